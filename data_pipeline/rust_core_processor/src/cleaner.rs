@@ -1,8 +1,9 @@
 use regex::Regex;
 use once_cell::sync::Lazy;
 use std::collections::{HashSet, HashMap};
-use chrono::{DateTime, NaiveDateTime, NaiveDate, Utc, TimeZone};
+use chrono::{DateTime, Datelike, NaiveDateTime, NaiveDate, Utc, TimeZone};
 use serde_json::Value;
+use crate::config::{HeadingCase, ProcessingConfig};
 
 // Pre-compiled regex patterns for ultra-fast text cleaning
 static EXTRA_WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
@@ -28,6 +29,9 @@ static EMAIL_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b").unwrap()
 });
 
+// Fewer words than this give no meaningful readability score
+const MIN_READABILITY_WORDS: usize = 30;
+
 // Stop words for keyword filtering
 static STOP_WORDS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     [
@@ -42,19 +46,58 @@ static STOP_WORDS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     ].iter().copied().collect()
 });
 
+// Acronyms that contain vowels and so can't be told apart from words by shape alone
+static KNOWN_ACRONYMS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    [
+        "API", "APIS", "FAQ", "FAQS", "UI", "UX", "AI", "IO", "OS", "ID", "IDE", "SEO", "CEO",
+        "CTO", "USA", "EU", "UK", "UN", "NASA", "JSON", "YAML", "REST", "AJAX", "OAUTH",
+        "GUI", "URI", "URL", "URLS", "AWS", "GCP", "IOT", "EULA", "ETA", "AOT", "JIT",
+    ].iter().copied().collect()
+});
+
+// Short function words kept lowercase inside title-cased headings
+static TITLE_SMALL_WORDS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    [
+        "a", "an", "the", "and", "but", "or", "nor", "for", "so", "yet",
+        "as", "at", "by", "in", "of", "off", "on", "per", "to", "up", "via", "vs",
+    ].iter().copied().collect()
+});
+
 pub struct FastCleaner {
     max_chunk_size: usize,
     min_chunk_size: usize,
     overlap_size: usize,
+    strict_dates: bool,
+    two_digit_year_pivot: u32,
 }
 
 impl FastCleaner {
     pub fn new() -> Self {
         Self {
             max_chunk_size: 2500,
-            min_chunk_size: 100,
-            overlap_size: 50,
+            min_chunk_size: 50,
+            overlap_size: 0,
+            strict_dates: false,
+            two_digit_year_pivot: 69,
+        }
+    }
+
+    /// Cleaner using the chunk budget and date policy of the processing options
+    pub fn with_config(config: &ProcessingConfig) -> Self {
+        Self {
+            max_chunk_size: config.max_chunk_size,
+            min_chunk_size: config.min_chunk_size,
+            overlap_size: config.chunk_overlap,
+            ..Self::new()
         }
+        .with_date_policy(config.strict_dates, config.two_digit_year_pivot)
+    }
+
+    /// Apply the date options: reject two-digit years (`strict`) or expand them around `pivot`
+    pub fn with_date_policy(mut self, strict: bool, pivot: u32) -> Self {
+        self.strict_dates = strict;
+        self.two_digit_year_pivot = pivot;
+        self
     }
 
     /// Main text cleaning function - REVISED for targeted cleaning
@@ -108,23 +151,130 @@ impl FastCleaner {
         cleaned = EXTRA_WHITESPACE.replace_all(&cleaned, " ").to_string();
         
         // Trim and ensure reasonable length
-        cleaned = cleaned.trim().to_string();
-        if cleaned.len() > 300 {
-            // Find a good breaking point
-            if let Some(pos) = cleaned[..300].rfind('.') {
-                cleaned = cleaned[..pos + 1].to_string();
-            } else if let Some(pos) = cleaned[..300].rfind(' ') {
-                cleaned = format!("{}...", &cleaned[..pos]);
+        self.make_preview(cleaned.trim(), 300)
+    }
+
+    /// Truncate text to at most `max_chars` characters at a natural boundary.
+    /// Prefers the last sentence end within the limit (kept as-is), then the last
+    /// word boundary, then a hard cut; mid-sentence cuts get a "..." suffix.
+    pub fn make_preview(&self, text: &str, max_chars: usize) -> String {
+        let text = text.trim();
+        if text.chars().count() <= max_chars {
+            return text.to_string();
+        }
+
+        // Byte offset of the max_chars-th character, so slicing never splits UTF-8
+        let limit = text.char_indices().nth(max_chars).map(|(i, _)| i).unwrap_or(text.len());
+        let window = &text[..limit];
+
+        let sentence_end = window
+            .char_indices()
+            .filter(|(i, c)| {
+                matches!(c, '.' | '!' | '?')
+                    && text[i + c.len_utf8()..].starts_with(char::is_whitespace)
+            })
+            .map(|(i, c)| i + c.len_utf8())
+            .next_back();
+        if let Some(end) = sentence_end {
+            return window[..end].to_string();
+        }
+
+        // Leave room for the ellipsis within the budget
+        let ellipsis_limit = text.char_indices().nth(max_chars.saturating_sub(3)).map(|(i, _)| i).unwrap_or(0);
+        let ellipsis_window = &text[..ellipsis_limit];
+        match ellipsis_window.rfind(char::is_whitespace) {
+            Some(pos) if pos > 0 => format!("{}...", ellipsis_window[..pos].trim_end()),
+            _ => format!("{}...", ellipsis_window),
+        }
+    }
+
+    /// Re-case an ALL-CAPS heading to title or sentence case, keeping acronyms
+    /// ("GETTING STARTED WITH THE API" -> "Getting Started With the API").
+    /// Mixed-case headings are returned unchanged to protect proper nouns.
+    pub fn normalize_heading_case(&self, text: &str, case: HeadingCase) -> String {
+        let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+        let is_all_caps = letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase());
+        if case == HeadingCase::Preserve || !is_all_caps {
+            return text.to_string();
+        }
+
+        let words: Vec<&str> = text.split(' ').collect();
+        let last_index = words.len().saturating_sub(1);
+
+        words.iter().enumerate().map(|(i, word)| {
+            let core = word.trim_matches(|c: char| !c.is_alphanumeric());
+            if Self::is_acronym(core) {
+                return word.to_string();
+            }
+
+            let lower = word.to_lowercase();
+            let capitalize = match case {
+                HeadingCase::Title => i == 0 || i == last_index || !TITLE_SMALL_WORDS.contains(core.to_lowercase().as_str()),
+                _ => i == 0,
+            };
+            if capitalize { Self::capitalize_first_letter(&lower) } else { lower }
+        }).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Helper: An all-caps token is an acronym if it's a known one, has no vowels
+    /// ("HTTP", "CSS"), or mixes in digits ("MP3")
+    fn is_acronym(token: &str) -> bool {
+        if token.chars().filter(|c| c.is_alphabetic()).count() < 2 {
+            return false;
+        }
+        KNOWN_ACRONYMS.contains(token)
+            || !token.chars().any(|c| "AEIOUY".contains(c))
+            || token.chars().any(|c| c.is_ascii_digit())
+    }
+
+    fn capitalize_first_letter(word: &str) -> String {
+        let mut result = String::with_capacity(word.len());
+        let mut capitalized = false;
+        for ch in word.chars() {
+            if !capitalized && ch.is_alphabetic() {
+                result.extend(ch.to_uppercase());
+                capitalized = true;
             } else {
-                cleaned = format!("{}...", &cleaned[..297]);
+                result.push(ch);
             }
         }
+        result
+    }
 
-        cleaned
+    /// Split text into sentence-aligned chunks of at most `max_chunk_size` bytes, dropping
+    /// pieces under `min_chunk_size`; each new chunk starts with up to the last
+    /// `overlap_size` bytes (whole words) of the text before it, as room allows
+    pub fn create_chunks(&self, text: &str) -> Vec<String> {
+        self.split_chunks(text, self.max_chunk_size, self.min_chunk_size)
     }
 
-    /// Create optimized text chunks for search indexing
-    pub fn create_chunks(&self, text: &str, max_size: usize, min_size: usize) -> Vec<String> {
+    /// Opening of the chunk that follows `finished`: its overlapping tail, then `next`.
+    /// The tail is shortened so the opening stays within `max_size` bytes.
+    fn start_chunk(&self, finished: &str, next: &str, max_size: usize) -> String {
+        let budget = self.overlap_size.min(max_size.saturating_sub(next.len() + 1));
+        let carry = if budget == 0 { "" } else { Self::word_tail(finished.trim(), budget) };
+        if carry.is_empty() { next.to_string() } else { format!("{} {}", carry, next) }
+    }
+
+    /// Last whole words of `text` fitting in `max_bytes`
+    fn word_tail(text: &str, max_bytes: usize) -> &str {
+        if text.len() <= max_bytes {
+            return text;
+        }
+        let mut start = text.len() - max_bytes;
+        while !text.is_char_boundary(start) {
+            start += 1;
+        }
+        let tail = &text[start..];
+        // Skip the partial word the cut landed in, unless the cut fell on a word boundary
+        if text[..start].ends_with(char::is_whitespace) {
+            tail.trim_start()
+        } else {
+            tail.split_once(char::is_whitespace).map_or("", |(_, rest)| rest.trim_start())
+        }
+    }
+
+    fn split_chunks(&self, text: &str, max_size: usize, min_size: usize) -> Vec<String> {
         if text.len() <= max_size {
             if text.len() >= min_size {
                 return vec![text.to_string()];
@@ -150,7 +300,7 @@ impl FastCleaner {
                 if current_chunk.len() >= min_size {
                     chunks.push(current_chunk.trim().to_string());
                 }
-                current_chunk = sentence_with_period;
+                current_chunk = self.start_chunk(&current_chunk, &sentence_with_period, max_size);
             } else {
                 if !current_chunk.is_empty() {
                     current_chunk.push(' ');
@@ -183,7 +333,7 @@ impl FastCleaner {
                 if current_chunk.len() >= min_size {
                     chunks.push(current_chunk.trim().to_string());
                 }
-                current_chunk = word.to_string();
+                current_chunk = self.start_chunk(&current_chunk, word, max_size);
             } else {
                 if !current_chunk.is_empty() {
                     current_chunk.push(' ');
@@ -305,14 +455,31 @@ impl FastCleaner {
         max_repeat > 10  // Increased from 5 to 10
     }
 
-    /// Extract and filter keywords from text
+    /// Case folding for matching text against a vocabulary: lowercasing plus the cases it
+    /// leaves unequal. Turkish "İ" (which lowercases to "i" + U+0307) and dotless "ı" fold
+    /// to "i", "ß" to "ss" and final "ς" to "σ".
+    pub fn fold_case(text: &str) -> String {
+        let mut folded = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                'İ' | 'ı' => folded.push('i'),
+                'ß' | 'ẞ' => folded.push_str("ss"),
+                'ς' => folded.push('σ'),
+                _ => folded.extend(c.to_lowercase()),
+            }
+        }
+        folded
+    }
+
+    /// Extract and filter keywords from text: words seen at least twice, most frequent
+    /// first, equally frequent words in alphabetical order
     pub fn extract_keywords(&self, text: &str, max_keywords: usize) -> Vec<String> {
         if text.is_empty() {
             return Vec::new();
         }
 
         // Simple but effective keyword extraction
-        let text_lower = text.to_lowercase();
+        let text_lower = Self::fold_case(text);
         let words: Vec<&str> = text_lower
             .split_whitespace()
             .filter(|word| {
@@ -328,9 +495,9 @@ impl FastCleaner {
             *word_counts.entry(word).or_insert(0) += 1;
         }
 
-        // Sort by frequency and take top keywords
+        // Frequency descending, ties alphabetical, so the order never depends on hashing
         let mut sorted_words: Vec<_> = word_counts.into_iter().collect();
-        sorted_words.sort_by(|a, b| b.1.cmp(&a.1));
+        sorted_words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
 
         sorted_words
             .into_iter()
@@ -340,7 +507,11 @@ impl FastCleaner {
             .collect()
     }
 
-    /// Normalize a date string to ISO 8601 format with Z suffix for OpenSearch compatibility
+    /// Normalize a date string to ISO 8601 format with Z suffix for OpenSearch compatibility.
+    ///
+    /// Pitfall: a two-digit year ("01/02/49") names no century. Lenient mode (the default)
+    /// maps years below `two_digit_year_pivot` to 20xx and the rest to 19xx, so the pivot
+    /// decides whether "49" is 2049 or 1949; strict mode returns None for such dates instead.
     pub fn normalize_date(&self, date_str: &str) -> Option<String> {
         if date_str.is_empty() {
             return None;
@@ -351,16 +522,187 @@ impl FastCleaner {
             return None;
         }
 
+        let mut utc_dt = Self::parse_date_utc(trimmed)?;
+
+        // %Y happily reads "49" as year 49, and RFC 2822 applies its own century rule
+        if utc_dt.year() < 100 || Self::is_rfc2822_two_digit_year(trimmed) {
+            if self.strict_dates {
+                return None;
+            }
+            let two_digit = (utc_dt.year() % 100) as u32;
+            let century = if two_digit < self.two_digit_year_pivot { 2000 } else { 1900 };
+            utc_dt = utc_dt.with_year(century + two_digit as i32)?;
+        }
+
+        Some(utc_dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+    }
+
+    /// Whole days between normalized (UTC ISO-8601) dates: `now` minus the last update
+    /// (modified, else published) when `now` is given, otherwise modified minus published.
+    /// None when a needed date is missing or the span is negative (inconsistent metadata).
+    pub fn staleness_days(published: Option<&str>, modified: Option<&str>, now: Option<DateTime<Utc>>) -> Option<i64> {
+        let parse = |date: &str| DateTime::parse_from_rfc3339(date).ok().map(|dt| dt.with_timezone(&Utc));
+        let published = published.and_then(parse);
+        let modified = modified.and_then(parse);
+
+        let (from, to) = match now {
+            Some(now) => (modified.or(published)?, now),
+            None => (published?, modified?),
+        };
+        let days = (to - from).num_days();
+        (days >= 0).then_some(days)
+    }
+
+    /// Decode named (amp, lt, gt, quot, apos, nbsp) and numeric character references;
+    /// anything else is left as written
+    pub fn decode_entities(text: &str) -> String {
+        let mut decoded = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(amp) = rest.find('&') {
+            decoded.push_str(&rest[..amp]);
+            let candidate = &rest[amp..];
+            let entity_end = candidate.find(';').filter(|&end| end <= 10);
+
+            let replacement = entity_end.and_then(|end| {
+                let entity = &candidate[1..end];
+                match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some(' '),
+                    _ => entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+                        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                        .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                        .and_then(char::from_u32),
+                }
+            });
+
+            match (replacement, entity_end) {
+                (Some(ch), Some(end)) => {
+                    decoded.push(ch);
+                    rest = &candidate[end + 1..];
+                }
+                _ => {
+                    decoded.push('&');
+                    rest = &candidate[1..];
+                }
+            }
+        }
+
+        decoded.push_str(rest);
+        decoded
+    }
+
+    /// Light normalization for structured-data strings: entities decoded, whitespace collapsed
+    pub fn normalize_value_text(text: &str) -> String {
+        Self::decode_entities(text).split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Apply `normalize_value_text` to every string in a JSON value (object keys, numbers
+    /// and other scalars are left alone)
+    pub fn normalize_json_strings(value: &mut Value) {
+        match value {
+            Value::String(text) => *text = Self::normalize_value_text(text),
+            Value::Array(items) => items.iter_mut().for_each(Self::normalize_json_strings),
+            Value::Object(obj) => obj.values_mut().for_each(Self::normalize_json_strings),
+            _ => {}
+        }
+    }
+
+    /// Index-style token count: runs of letters/digits, split on whitespace and punctuation
+    pub fn count_tokens(text: &str) -> usize {
+        text.split(|c: char| !c.is_alphanumeric()).filter(|t| !t.is_empty()).count()
+    }
+
+    /// Words carrying information: tokens that are neither stopwords nor numbers
+    pub fn count_content_words(text: &str) -> usize {
+        text.split_whitespace()
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+            .filter(|word| word.chars().any(char::is_alphabetic) && !STOP_WORDS.contains(word.as_str()))
+            .count()
+    }
+
+    /// Flesch reading ease (higher is easier; 60-70 is plain English) for prose in `text`.
+    /// Syllables are estimated from vowel groups. None below MIN_READABILITY_WORDS words,
+    /// where the score is too noisy to mean anything.
+    pub fn flesch_reading_ease(text: &str) -> Option<f64> {
+        Self::readability(text).map(|(ease, _)| ease)
+    }
+
+    /// (Flesch reading ease, Flesch-Kincaid grade level) of `text` from a single count of its
+    /// words, sentences and syllables. The grade is the US school grade needed to follow the
+    /// text; None below MIN_READABILITY_WORDS words, as for `flesch_reading_ease`.
+    pub fn readability(text: &str) -> Option<(f64, f64)> {
+        let (words_per_sentence, syllables_per_word) = Self::readability_rates(text)?;
+        let ease = 206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word;
+        let grade = 0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59;
+        Some(((ease * 10.0).round() / 10.0, (grade * 10.0).round() / 10.0))
+    }
+
+    // (words per sentence, syllables per word) of the prose in `text`
+    fn readability_rates(text: &str) -> Option<(f64, f64)> {
+        let words: Vec<&str> = text.split_whitespace()
+            .filter(|w| w.chars().any(|c| c.is_alphabetic()))
+            .collect();
+        if words.len() < MIN_READABILITY_WORDS {
+            return None;
+        }
+        let sentences = text.split(['.', '!', '?'])
+            .filter(|s| s.chars().any(|c| c.is_alphabetic()))
+            .count()
+            .max(1);
+        let syllables: usize = words.iter().map(|w| Self::estimate_syllables(w)).sum();
+        Some((words.len() as f64 / sentences as f64, syllables as f64 / words.len() as f64))
+    }
+
+    // Vowel groups, less a silent final "e"; at least one per word
+    fn estimate_syllables(word: &str) -> usize {
+        let word: String = word.chars().filter(|c| c.is_alphabetic()).flat_map(char::to_lowercase).collect();
+        let mut groups = 0;
+        let mut previous_vowel = false;
+        for c in word.chars() {
+            let vowel = matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+            if vowel && !previous_vowel {
+                groups += 1;
+            }
+            previous_vowel = vowel;
+        }
+        if word.ends_with('e') && !word.ends_with("le") && groups > 1 {
+            groups -= 1;
+        }
+        groups.max(1)
+    }
+
+    /// True for RFC 2822 dates written with a two-digit year ("Fri, 22 Aug 25 ...")
+    fn is_rfc2822_two_digit_year(trimmed: &str) -> bool {
+        if DateTime::parse_from_rfc2822(trimmed).is_err() {
+            return false;
+        }
+        let mut tokens = trimmed.split_whitespace().peekable();
+        if tokens.peek().map(|t| t.ends_with(',')).unwrap_or(false) {
+            tokens.next();
+        }
+        tokens.nth(2)
+            .map(|year| year.len() <= 2 && year.chars().all(|c| c.is_ascii_digit()))
+            .unwrap_or(false)
+    }
+
+    /// Parse the date formats seen on the web into UTC, years taken as written
+    fn parse_date_utc(trimmed: &str) -> Option<DateTime<Utc>> {
+
         // 1. ISO 8601 formats (already correct)
         if let Ok(dt) = DateTime::parse_from_rfc3339(trimmed) {
             let utc_dt = dt.with_timezone(&Utc);
-            return Some(utc_dt.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+            return Some(utc_dt);
         }
 
         // 2. RFC 2822 format (e.g., "Fri, 22 Aug 2025 15:05:20 GMT")
         if let Ok(dt) = DateTime::parse_from_rfc2822(trimmed) {
             let utc_dt = dt.with_timezone(&Utc);
-            return Some(utc_dt.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+            return Some(utc_dt);
         }
 
         // 3. Common web formats with timezone
@@ -374,7 +716,7 @@ impl FastCleaner {
         for format in &web_formats_with_tz {
             if let Ok(dt) = DateTime::parse_from_str(trimmed, format) {
                 let utc_dt = dt.with_timezone(&Utc);
-                return Some(utc_dt.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+                return Some(utc_dt);
             }
         }
 
@@ -397,7 +739,7 @@ impl FastCleaner {
         for format in &naive_formats {
             if let Ok(ndt) = NaiveDateTime::parse_from_str(trimmed, format) {
                 let utc_dt = Utc.from_utc_datetime(&ndt);
-                return Some(utc_dt.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+                return Some(utc_dt);
             }
         }
 
@@ -416,7 +758,7 @@ impl FastCleaner {
         for format in &am_pm_formats {
             if let Ok(ndt) = NaiveDateTime::parse_from_str(trimmed, format) {
                 let utc_dt = Utc.from_utc_datetime(&ndt);
-                return Some(utc_dt.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+                return Some(utc_dt);
             }
         }
 
@@ -440,7 +782,7 @@ impl FastCleaner {
             if let Ok(nd) = NaiveDate::parse_from_str(trimmed, format) {
                 if let Some(ndt) = nd.and_hms_opt(0, 0, 0) {
                     let utc_dt = Utc.from_utc_datetime(&ndt);
-                    return Some(utc_dt.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+                    return Some(utc_dt);
                 }
             }
         }
@@ -449,6 +791,97 @@ impl FastCleaner {
         None
     }
 
+    /// Change-detection hash of `text`: lowercased, whitespace-collapsed, with every
+    /// `volatile` match replaced by a placeholder, then FNV-1a (stable across runs and
+    /// builds, unlike std's hasher). Returned as 16 hex digits.
+    pub fn stable_content_hash(&self, text: &str, volatile: &[Regex]) -> String {
+        let mut normalized = text.to_lowercase();
+        for pattern in volatile {
+            normalized = pattern.replace_all(&normalized, "#").into_owned();
+        }
+        let normalized = EXTRA_WHITESPACE.replace_all(normalized.trim(), " ");
+
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in normalized.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        format!("{:016x}", hash)
+    }
+
+    /// Parse an ISO-8601 duration (e.g. "PT1H30M", "P1DT2H") into total seconds.
+    /// Year/month components are rejected since their length in seconds is ambiguous.
+    pub fn parse_iso_duration(&self, duration_str: &str) -> Option<u64> {
+        let trimmed = duration_str.trim().to_uppercase();
+        let body = trimmed.strip_prefix('P')?;
+        if body.is_empty() {
+            return None;
+        }
+
+        let (date_part, time_part) = match body.split_once('T') {
+            Some((date, time)) => {
+                if time.is_empty() {
+                    return None;
+                }
+                (date, time)
+            }
+            None => (body, ""),
+        };
+
+        let date_seconds = Self::sum_duration_units(date_part, |unit| match unit {
+            'W' => Some(604_800.0),
+            'D' => Some(86_400.0),
+            _ => None, // Y and M (months) are ambiguous
+        })?;
+        let time_seconds = Self::sum_duration_units(time_part, |unit| match unit {
+            'H' => Some(3_600.0),
+            'M' => Some(60.0),
+            'S' => Some(1.0),
+            _ => None,
+        })?;
+
+        let total_seconds = date_seconds + time_seconds;
+        Some(total_seconds.round() as u64)
+    }
+
+    /// Helper: Sum "<number><unit>" pairs of one duration section, e.g. "1H30M"
+    fn sum_duration_units(section: &str, unit_seconds: impl Fn(char) -> Option<f64>) -> Option<f64> {
+        let mut total = 0.0;
+        let mut number = String::new();
+
+        for ch in section.chars() {
+            if ch.is_ascii_digit() || ch == '.' || ch == ',' {
+                number.push(if ch == ',' { '.' } else { ch });
+                continue;
+            }
+            let value: f64 = number.parse().ok()?;
+            number.clear();
+            total += value * unit_seconds(ch)?;
+        }
+
+        // A trailing number without a unit designator is malformed
+        if number.is_empty() { Some(total) } else { None }
+    }
+
+    /// Render a duration in seconds as a short human string (e.g. "1 hr 30 min")
+    pub fn format_duration(&self, total_seconds: u64) -> String {
+        let hours = total_seconds / 3_600;
+        let minutes = (total_seconds % 3_600) / 60;
+        let seconds = total_seconds % 60;
+
+        let mut parts = Vec::new();
+        if hours > 0 {
+            parts.push(format!("{} hr", hours));
+        }
+        if minutes > 0 {
+            parts.push(format!("{} min", minutes));
+        }
+        if seconds > 0 || parts.is_empty() {
+            parts.push(format!("{} sec", seconds));
+        }
+        parts.join(" ")
+    }
+
     /// Clean all date fields in structured data recursively
     pub fn clean_structured_data_dates(&self, value: &mut Value) {
         match value {
@@ -554,3 +987,122 @@ impl FastCleaner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunker(max_chunk_size: usize, chunk_overlap: usize) -> FastCleaner {
+        FastCleaner::with_config(&ProcessingConfig {
+            max_chunk_size,
+            min_chunk_size: 10,
+            chunk_overlap,
+            ..ProcessingConfig::default()
+        })
+    }
+
+    fn sample_text() -> String {
+        (1..=12)
+            .map(|i| format!("Sentence number {} talks about the garden path", i))
+            .collect::<Vec<_>>()
+            .join(". ")
+    }
+
+    #[test]
+    fn chunks_open_with_the_tail_of_the_previous_chunk() {
+        let chunks = chunker(160, 40).create_chunks(&sample_text());
+        assert!(chunks.len() > 2);
+        for pair in chunks.windows(2) {
+            let carried = FastCleaner::word_tail(&pair[0], 40);
+            assert!(!carried.is_empty());
+            assert!(pair[0].ends_with(carried));
+            assert!(pair[1].starts_with(carried), "{:?} does not start with {:?}", pair[1], carried);
+        }
+    }
+
+    #[test]
+    fn overlap_does_not_push_chunks_past_max_size() {
+        for overlap in [0, 40, 100, 150] {
+            for chunk in chunker(160, overlap).create_chunks(&sample_text()) {
+                assert!(chunk.len() <= 160, "overlap {}: {} bytes", overlap, chunk.len());
+            }
+        }
+    }
+
+    #[test]
+    fn no_overlap_keeps_chunks_disjoint() {
+        let chunks = chunker(160, 0).create_chunks(&sample_text());
+        assert!(chunks[1].starts_with("Sentence number"));
+    }
+
+    #[test]
+    fn turkish_dotted_and_dotless_i_fold_to_the_vocabulary_entry() {
+        assert_eq!(FastCleaner::fold_case("İSTANBUL"), "istanbul");
+        assert_eq!(FastCleaner::fold_case("DİYARBAKIR"), FastCleaner::fold_case("diyarbakir"));
+        assert_eq!(FastCleaner::fold_case("ılık"), "ilik");
+    }
+
+    #[test]
+    fn german_sharp_s_folds_to_ss() {
+        assert_eq!(FastCleaner::fold_case("STRASSE"), FastCleaner::fold_case("Straße"));
+        assert_eq!(FastCleaner::fold_case("GROẞ"), "gross");
+    }
+
+    #[test]
+    fn iso_durations_convert_to_seconds() {
+        let cleaner = FastCleaner::new();
+        assert_eq!(cleaner.parse_iso_duration("PT1H30M"), Some(5400));
+        assert_eq!(cleaner.format_duration(5400), "1 hr 30 min");
+    }
+
+    #[test]
+    fn all_caps_headings_are_title_cased_keeping_acronyms() {
+        let cleaner = FastCleaner::new();
+        assert_eq!(
+            cleaner.normalize_heading_case("GETTING STARTED WITH THE API", HeadingCase::Title),
+            "Getting Started With the API",
+        );
+        assert_eq!(cleaner.normalize_heading_case("Getting started", HeadingCase::Title), "Getting started");
+    }
+
+    #[test]
+    fn preview_cuts_at_the_last_sentence_end() {
+        let text = "First sentence here. Second sentence is longer than the limit allows.";
+        assert_eq!(FastCleaner::new().make_preview(text, 40), "First sentence here.");
+    }
+
+    #[test]
+    fn preview_falls_back_to_a_word_boundary_with_ellipsis() {
+        let text = "one long run of words without any sentence punctuation at all";
+        assert_eq!(FastCleaner::new().make_preview(text, 20), "one long run of...");
+    }
+
+    #[test]
+    fn preview_leaves_short_text_untouched() {
+        assert_eq!(FastCleaner::new().make_preview("  Short text  ", 40), "Short text");
+    }
+
+    #[test]
+    fn simple_prose_reads_easier_than_complex_prose() {
+        let simple = "The cat sat on the mat. It was a warm day. The dog ran to the park. \
+            We had fun in the sun. Then we went home to eat. The food was good and hot.";
+        let complex = "Notwithstanding considerable methodological heterogeneity, contemporary epidemiological \
+            investigations consistently demonstrate statistically significant associations between \
+            socioeconomic deprivation and cardiovascular morbidity, particularly among institutionalized \
+            populations experiencing intergenerational disadvantage and administrative marginalization. \
+            Comprehensive longitudinal interventions necessitate interdisciplinary collaboration, sophisticated \
+            infrastructural investment and continuous governmental accountability.";
+        let (simple_ease, simple_grade) = FastCleaner::readability(simple).unwrap();
+        let (complex_ease, complex_grade) = FastCleaner::readability(complex).unwrap();
+        assert!(simple_ease > 80.0, "{}", simple_ease);
+        assert!(complex_ease < 10.0, "{}", complex_ease);
+        assert!(simple_grade < complex_grade);
+        assert_eq!(FastCleaner::flesch_reading_ease(simple), Some(simple_ease));
+    }
+
+    #[test]
+    fn readability_needs_enough_words() {
+        assert_eq!(FastCleaner::readability("Too short to score."), None);
+        assert_eq!(FastCleaner::readability(""), None);
+    }
+}
@@ -0,0 +1,610 @@
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Pipeline stages that accept a soft time budget in `stage_budgets_ms`
+pub const BUDGETED_STAGES: &[&str] = &["structured_data", "annotation"];
+
+/// Soft time budget of one pipeline stage. Stages check it between units of work and
+/// drop their output once it runs out, so the rest of the document still completes.
+pub struct StageTimer {
+    started: Instant,
+    budget: Option<Duration>,
+}
+
+impl StageTimer {
+    pub fn new(budget: Option<Duration>) -> Self {
+        Self { started: Instant::now(), budget }
+    }
+
+    /// True once the stage has run longer than its budget; never without one
+    pub fn exceeded(&self) -> bool {
+        self.budget.is_some_and(|budget| self.started.elapsed() > budget)
+    }
+}
+
+/// How ALL-CAPS headings are re-cased; mixed-case headings keep the author's casing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadingCase {
+    Preserve,
+    Title,
+    Sentence,
+}
+
+impl HeadingCase {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value.to_lowercase().as_str() {
+            "preserve" => Ok(Self::Preserve),
+            "title" => Ok(Self::Title),
+            "sentence" => Ok(Self::Sentence),
+            other => Err(PyValueError::new_err(format!(
+                "heading_case must be 'preserve', 'title' or 'sentence', got '{}'", other
+            ))),
+        }
+    }
+}
+
+/// Where <abbr>/<acronym> title expansions go; dropped by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbbreviationMode {
+    Off,
+    /// "HTML (HyperText Markup Language)" in main_content
+    Inline,
+    /// Separate `abbreviations` map of abbreviation -> expansion
+    Synonyms,
+}
+
+impl AbbreviationMode {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "inline" => Ok(Self::Inline),
+            "synonyms" => Ok(Self::Synonyms),
+            other => Err(PyValueError::new_err(format!(
+                "abbreviations must be 'off', 'inline' or 'synonyms', got '{}'", other
+            ))),
+        }
+    }
+}
+
+/// When forum / Q&A thread extraction runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadMode {
+    /// Only on pages typed as Q&A or forum threads (content_type or schema.org markup)
+    Auto,
+    /// Caller hint that the page is a thread; always try
+    Force,
+    Off,
+}
+
+impl ThreadMode {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "force" => Ok(Self::Force),
+            "off" => Ok(Self::Off),
+            other => Err(PyValueError::new_err(format!(
+                "thread_mode must be 'auto', 'force' or 'off', got '{}'", other
+            ))),
+        }
+    }
+}
+
+/// Thresholds for the chunk-quality gates (is_chunk_meaningful / contains_web_noise).
+/// Ratios are fractions of the chunk's characters or words.
+#[derive(Debug, Clone)]
+pub struct ChunkFilterThresholds {
+    pub min_chars: usize,
+    pub min_words: usize,
+    pub min_alpha_ratio: f32,
+    pub max_json_ratio: f32,
+    pub max_interface_noise_ratio: f32,
+    pub max_css_density: f32,
+    pub max_brackets: usize,
+    pub max_uppercase_ratio: f32,
+    pub max_navigation_ratio: f32,
+}
+
+impl Default for ChunkFilterThresholds {
+    fn default() -> Self {
+        Self {
+            min_chars: 20,
+            min_words: 3,
+            min_alpha_ratio: 0.2,
+            max_json_ratio: 0.33,
+            max_interface_noise_ratio: 0.2,
+            max_css_density: 0.01,
+            max_brackets: 10,
+            max_uppercase_ratio: 0.25,
+            max_navigation_ratio: 0.3,
+        }
+    }
+}
+
+impl ChunkFilterThresholds {
+    /// Override defaults from a `chunk_filter={...}` dict, rejecting unknown keys
+    fn from_py_dict(values: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let mut thresholds = Self::default();
+        for (key, value) in values.iter() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "min_chars" => thresholds.min_chars = value.extract()?,
+                "min_words" => thresholds.min_words = value.extract()?,
+                "min_alpha_ratio" => thresholds.min_alpha_ratio = value.extract()?,
+                "max_json_ratio" => thresholds.max_json_ratio = value.extract()?,
+                "max_interface_noise_ratio" => thresholds.max_interface_noise_ratio = value.extract()?,
+                "max_css_density" => thresholds.max_css_density = value.extract()?,
+                "max_brackets" => thresholds.max_brackets = value.extract()?,
+                "max_uppercase_ratio" => thresholds.max_uppercase_ratio = value.extract()?,
+                "max_navigation_ratio" => thresholds.max_navigation_ratio = value.extract()?,
+                other => {
+                    return Err(PyTypeError::new_err(format!("unknown chunk_filter threshold '{}'", other)));
+                }
+            }
+        }
+        Ok(thresholds)
+    }
+}
+
+/// Tunable processing options, passed from Python as `process_html(..., **options)`.
+/// Every default reproduces the behaviour the pipeline had before the option existed,
+/// except `min_category_score`, whose default drops single incidental category matches.
+#[derive(Debug, Clone)]
+pub struct ProcessingConfig {
+    /// Minimum number of alphanumeric characters a heading needs to be kept
+    pub min_heading_length: usize,
+    /// Also return the extracted text before FastCleaner runs, as `main_content_raw`
+    pub include_raw_content: bool,
+    /// Also return the inner HTML of the selected content region(s), as `main_content_html`
+    pub include_content_html: bool,
+    /// Case-insensitive template markers ("[insert title here]") that flag unfilled CMS pages
+    pub placeholder_patterns: Vec<String>,
+    /// Re-casing policy for ALL-CAPS headings (acronyms such as "API" are kept)
+    pub heading_case: HeadingCase,
+    /// Drop repeated boilerplate paragraphs (disclaimers, CTAs) from main_content, keeping the first
+    pub dedupe_paragraphs: bool,
+    /// Force the document language (ISO-639-1, e.g. "es") instead of detecting it
+    pub language: Option<String>,
+    /// Wrap <strong>/<b> spans as **bold** and <em>/<i> spans as *italic* in main_content
+    pub emphasis_markers: bool,
+    /// Thresholds for dropping noisy or low-content chunks
+    pub chunk_filter: ChunkFilterThresholds,
+    /// Report why each dropped chunk was rejected, as `chunk_diagnostics`
+    pub chunk_diagnostics: bool,
+    /// If filtering leaves no chunks, emit the unfiltered (cleaned) chunks flagged `low_confidence`
+    pub guarantee_chunks: bool,
+    /// Detect the language from main_content (falling back to the largest text block)
+    /// instead of the whole page, reported as `content_language`
+    pub detect_content_language: bool,
+    /// Clean and filter chunks on the rayon pool once a document has more chunks than this
+    pub parallel_chunk_threshold: usize,
+    /// Reject dates with two-digit years instead of guessing the century
+    pub strict_dates: bool,
+    /// Lenient mode: two-digit years below this become 20xx, the rest 19xx (chrono's %y rule)
+    pub two_digit_year_pivot: u32,
+    /// Most JSON-LD scripts parsed per page; the rest are counted in `json_ld_blocks_skipped`
+    pub max_json_ld_blocks: usize,
+    /// Total bytes of JSON-LD parsed per page
+    pub max_json_ld_bytes: usize,
+    /// Prefix blockquote text in main_content with "> " so quoted material stays recognizable
+    pub quote_markers: bool,
+    /// Regexes blanked out of main_content before `content_hash` is computed, so counters,
+    /// timestamps and relative dates don't change the hash between crawls
+    pub volatile_patterns: Vec<String>,
+    /// Keep <abbr title="..."> expansions inline in main_content or as a synonyms map
+    pub abbreviations: AbbreviationMode,
+    /// Primary-image candidates with a declared width (height) below this are skipped;
+    /// candidates without declared dimensions are kept
+    pub min_image_width: u32,
+    pub min_image_height: u32,
+    /// Also return `content_type_confidence` and ranked `content_type_alternatives`
+    pub content_type_alternatives: bool,
+    /// Render <table>s as GitHub-flavored markdown pipe tables at their position in main_content
+    pub markdown_tables: bool,
+    /// Case-insensitive phrases of soft-404 pages ("page not found"); a thin page whose title
+    /// or body carries one is flagged `is_error_page`
+    pub error_page_patterns: Vec<String>,
+    /// Record silent fallbacks and drops ("json_ld_parse_failed", "date_unparseable:<value>",
+    /// "body_fallback_used", ...) in `warnings`
+    pub collect_warnings: bool,
+    /// Extra content-container selectors (e.g. "app-article-body", ".cms-story"), tried
+    /// before the built-in main-content selectors
+    pub content_selectors: Vec<String>,
+    /// Regex-heavy scoring and annotation passes (technical score, categories, quality
+    /// indicators, low-effort score) only look at the first this-many bytes of main_content
+    pub max_analysis_bytes: usize,
+    /// Leave "related articles" / "recommended" widgets out of main_content
+    pub exclude_related_blocks: bool,
+    /// Class/id fragments that mark such widgets; matched against hyphen/underscore-separated
+    /// parts of each class and id, so "related-posts" matches but "unrelated" does not
+    pub related_block_patterns: Vec<String>,
+    /// Chunk main_content into text_chunks_with_context. Not a Python option: process_html_fields
+    /// turns it off when no chunk-derived field is requested
+    pub build_chunks: bool,
+    /// Reference time for `content_staleness_days` (RFC 3339, e.g. the crawl time); without
+    /// it staleness is the modified - published gap
+    pub now: Option<DateTime<Utc>>,
+    /// Lowercase term -> synonyms used to expand extracted keywords ("js" -> ["javascript"]);
+    /// expansions are returned separately as `keyword_expansions`
+    pub keyword_synonyms: HashMap<String, Vec<String>>,
+    /// Most synonym expansions added per document
+    pub max_keyword_expansions: usize,
+    /// When the DOM yields fewer words than this, main_content is recovered from app-state
+    /// JSON (__NEXT_DATA__, window.__INITIAL_STATE__) if that has more; 0 (the default)
+    /// disables, e.g. 50 suits client-rendered sites
+    pub app_state_min_words: usize,
+    /// Keyword score a content category needs before it is assigned; 1 admits any
+    /// single incidental keyword match, as categorisation did before this option
+    pub min_category_score: usize,
+    /// Hosts refused before parsing ("denied_domain"): "example.com" matches that host only,
+    /// ".example.com" or "*.example.com" also matches every subdomain
+    pub denied_domains: Vec<String>,
+    /// Replace math in main_content with "[math:N]" placeholders indexing `math_blocks`
+    pub math_placeholders: bool,
+    /// When title, H1 and description are identical, replace the description with the
+    /// lead paragraph (or the start of main_content)
+    pub derive_description: bool,
+    /// Also return `token_counts` (main_content, title, description, chunks) for index sizing
+    pub token_counts: bool,
+    /// Extract forum / Q&A posts into `thread`
+    pub thread_mode: ThreadMode,
+    /// Decode entities and collapse whitespace in every JSON-LD / microdata string value
+    pub normalize_structured_data: bool,
+    /// Compute the heuristic `low_effort_score` and apply it as a mild quality penalty
+    pub detect_low_effort: bool,
+    /// Also return `responsive_images`: every srcset candidate of each image
+    pub responsive_images: bool,
+    /// Also return `phone_numbers`: tel: links and numbers in the page text, E.164-normalized
+    pub phone_numbers: bool,
+    /// Remove U+FFFD replacement characters (mis-decoded bytes) from main_content and
+    /// chunks; `has_encoding_errors` / `encoding_error_count` are reported either way
+    pub strip_encoding_errors: bool,
+    /// Template for each chunk's `contextualized_text` (e.g. "{title} > {section}: {chunk}"),
+    /// for embedding with context; {section} is the section path joined with " > "
+    pub chunk_context_template: Option<String>,
+    /// Short main-content blocks containing one of these phrases (case-insensitive) are
+    /// dropped as site boilerplate, e.g. ["privacy policy", "all rights reserved"];
+    /// empty (nothing dropped) by default
+    pub boilerplate_phrases: Vec<String>,
+    /// Largest chunk in bytes; size it to the embedding model's window
+    pub max_chunk_size: usize,
+    /// Chunks (and whole documents) shorter than this many bytes are dropped
+    pub min_chunk_size: usize,
+    /// Bytes of the previous chunk's tail (whole words) repeated at the start of the next
+    pub chunk_overlap: usize,
+    /// Newspaper sections `news_section` may report; other section names are ignored
+    pub news_sections: Vec<String>,
+    /// Score length on content words (no stopwords or numbers) so filler does not pass for depth
+    pub content_word_length: bool,
+    /// Also return `definitions`: the (term, definition) pairs of <dl> lists, in order
+    pub definitions: bool,
+    /// Render <dl> lists as "**term**: definition" lines at their position in main_content
+    pub markdown_definitions: bool,
+    /// Data tables returned in `tables`, in document order; 0 disables table extraction
+    pub max_tables: usize,
+    /// Render <pre> blocks as ```-fenced code (with their language) at their position in
+    /// main_content; `code_blocks` carries the verbatim source either way
+    pub fenced_code: bool,
+    /// Soft per-stage budgets in milliseconds, keyed by `BUDGETED_STAGES` name. A stage
+    /// that overruns is skipped (its output dropped, warning "stage_budget_exceeded:<stage>")
+    pub stage_budgets_ms: HashMap<String, u64>,
+}
+
+impl Default for ProcessingConfig {
+    fn default() -> Self {
+        Self {
+            min_heading_length: 2,
+            include_raw_content: false,
+            include_content_html: false,
+            placeholder_patterns: [
+                "[insert", "insert title here", "insert text here", "your content here",
+                "your text here", "add your content", "placeholder text", "sample text goes here",
+                "this is a sample page", "edit this text",
+            ].iter().map(|p| p.to_string()).collect(),
+            heading_case: HeadingCase::Preserve,
+            dedupe_paragraphs: false,
+            language: None,
+            emphasis_markers: false,
+            chunk_filter: ChunkFilterThresholds::default(),
+            chunk_diagnostics: false,
+            guarantee_chunks: false,
+            detect_content_language: false,
+            parallel_chunk_threshold: 32,
+            strict_dates: false,
+            two_digit_year_pivot: 69,
+            max_json_ld_blocks: 32,
+            max_json_ld_bytes: 1024 * 1024,
+            quote_markers: false,
+            volatile_patterns: [
+                // "1,234 views", "12K likes", "3 comments"
+                r"(?i)\b\d[\d,.]*\s*[km]?\s+(?:views?|likes?|shares?|comments?|reads?|followers?|subscribers?|votes?|points?)\b",
+                // "5 minutes ago"
+                r"(?i)\b\d+\s+(?:seconds?|secs?|minutes?|mins?|hours?|hrs?|days?|weeks?|months?|years?)\s+ago\b",
+                // "2024-03-05", "2024-03-05T10:00:00"
+                r"\b\d{4}-\d{2}-\d{2}(?:[T ]\d{2}:\d{2}(?::\d{2})?)?",
+                // "03/05/2024", "5.3.24"
+                r"\b\d{1,2}[/.]\d{1,2}[/.]\d{2,4}\b",
+                // "March 5, 2024", "5 Mar 2024"
+                r"(?i)\b(?:jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*\.?\s+\d{1,2},?\s+\d{4}\b",
+                r"(?i)\b\d{1,2}\s+(?:jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*\.?,?\s+\d{4}\b",
+                // "10:42", "9:05 pm"
+                r"(?i)\b\d{1,2}:\d{2}(?::\d{2})?\s*(?:am|pm)?\b",
+            ].iter().map(|p| p.to_string()).collect(),
+            abbreviations: AbbreviationMode::Off,
+            min_image_width: 0,
+            min_image_height: 0,
+            content_type_alternatives: false,
+            markdown_tables: false,
+            error_page_patterns: [
+                "page not found", "404 not found", "error 404", "404 error", "410 gone",
+                "no longer exists", "no longer available", "could not be found", "cannot be found",
+                "can't be found", "page you requested", "page you were looking for",
+                "page you are looking for", "page doesn't exist", "page does not exist",
+            ].iter().map(|p| p.to_string()).collect(),
+            collect_warnings: false,
+            content_selectors: Vec::new(),
+            max_analysis_bytes: 256 * 1024,
+            exclude_related_blocks: false,
+            related_block_patterns: [
+                "related", "recommended", "recommendations", "you-may-like", "you-might-like",
+                "more-stories", "read-next", "also-read", "more-from", "outbrain", "taboola",
+            ].iter().map(|p| p.to_string()).collect(),
+            build_chunks: true,
+            now: None,
+            keyword_synonyms: HashMap::new(),
+            max_keyword_expansions: 10,
+            app_state_min_words: 0,
+            min_category_score: 2,
+            denied_domains: Vec::new(),
+            math_placeholders: false,
+            derive_description: false,
+            token_counts: false,
+            thread_mode: ThreadMode::Auto,
+            normalize_structured_data: false,
+            detect_low_effort: false,
+            responsive_images: false,
+            phone_numbers: false,
+            strip_encoding_errors: false,
+            chunk_context_template: None,
+            boilerplate_phrases: Vec::new(),
+            max_chunk_size: 2500,
+            min_chunk_size: 50,
+            chunk_overlap: 0,
+            news_sections: [
+                "politics", "business", "technology", "science", "health", "sports", "opinion",
+                "world", "entertainment", "culture", "travel", "education", "environment", "lifestyle",
+            ].iter().map(|s| s.to_string()).collect(),
+            content_word_length: false,
+            definitions: false,
+            markdown_definitions: false,
+            max_tables: 10,
+            fenced_code: false,
+            stage_budgets_ms: HashMap::new(),
+        }
+    }
+}
+
+impl ProcessingConfig {
+    /// Prefix of `text` the analysis passes may scan, cut at a char boundary
+    pub fn analysis_sample<'t>(&self, text: &'t str) -> &'t str {
+        if text.len() <= self.max_analysis_bytes {
+            return text;
+        }
+        let mut end = self.max_analysis_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        &text[..end]
+    }
+
+    /// Whether main_content carries line-break placeholders (markdown tables, definition
+    /// lists, fenced code) to restore after cleaning
+    pub fn uses_line_breaks(&self) -> bool {
+        self.markdown_tables || self.markdown_definitions || self.fenced_code
+    }
+
+    /// Every optional output turned on (raw content, diagnostics, warnings, alternatives,
+    /// token counts, abbreviation map, content language). Options that rewrite
+    /// main_content or drop material keep their defaults.
+    pub fn all_outputs() -> Self {
+        Self {
+            include_raw_content: true,
+            include_content_html: true,
+            chunk_diagnostics: true,
+            detect_content_language: true,
+            content_type_alternatives: true,
+            collect_warnings: true,
+            token_counts: true,
+            responsive_images: true,
+            phone_numbers: true,
+            abbreviations: AbbreviationMode::Synonyms,
+            ..Self::default()
+        }
+    }
+
+    /// Budget of `stage`, started now
+    pub fn stage_timer(&self, stage: &str) -> StageTimer {
+        StageTimer::new(self.stage_budgets_ms.get(stage).map(|ms| Duration::from_millis(*ms)))
+    }
+
+    /// True when the host of `url` is on the deny-list
+    pub fn is_denied_domain(&self, url: &str) -> bool {
+        if self.denied_domains.is_empty() {
+            return false;
+        }
+        let Some(host) = url::Url::parse(url).ok().and_then(|u| u.host_str().map(|h| h.to_lowercase())) else {
+            return false;
+        };
+        self.denied_domains.iter().any(|entry| {
+            match entry.strip_prefix("*.").or_else(|| entry.strip_prefix('.')) {
+                Some(suffix) => host == suffix || host.ends_with(&format!(".{}", suffix)),
+                None => host == *entry,
+            }
+        })
+    }
+
+    /// Build a config from Python keyword options, rejecting unknown keys
+    pub fn from_py_options(options: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let mut config = Self::default();
+        let Some(options) = options else {
+            return Ok(config);
+        };
+
+        for (key, value) in options.iter() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "min_heading_length" => config.min_heading_length = value.extract()?,
+                "include_raw_content" => config.include_raw_content = value.extract()?,
+                "include_content_html" => config.include_content_html = value.extract()?,
+                "placeholder_patterns" => {
+                    let patterns: Vec<String> = value.extract()?;
+                    config.placeholder_patterns = patterns.iter().map(|p| p.to_lowercase()).collect();
+                }
+                "heading_case" => config.heading_case = HeadingCase::parse(&value.extract::<String>()?)?,
+                "dedupe_paragraphs" => config.dedupe_paragraphs = value.extract()?,
+                "language" => config.language = value.extract()?,
+                "emphasis_markers" => config.emphasis_markers = value.extract()?,
+                "chunk_filter" => config.chunk_filter = ChunkFilterThresholds::from_py_dict(value.downcast::<PyDict>()?)?,
+                "chunk_diagnostics" => config.chunk_diagnostics = value.extract()?,
+                "guarantee_chunks" => config.guarantee_chunks = value.extract()?,
+                "detect_content_language" => config.detect_content_language = value.extract()?,
+                "parallel_chunk_threshold" => config.parallel_chunk_threshold = value.extract()?,
+                "strict_dates" => config.strict_dates = value.extract()?,
+                "max_json_ld_blocks" => config.max_json_ld_blocks = value.extract()?,
+                "max_json_ld_bytes" => config.max_json_ld_bytes = value.extract()?,
+                "quote_markers" => config.quote_markers = value.extract()?,
+                "volatile_patterns" => {
+                    let patterns: Vec<String> = value.extract()?;
+                    if let Some(err) = patterns.iter().find_map(|p| regex::Regex::new(p).err()) {
+                        return Err(PyValueError::new_err(format!("invalid volatile pattern: {}", err)));
+                    }
+                    config.volatile_patterns = patterns;
+                }
+                "abbreviations" => config.abbreviations = AbbreviationMode::parse(&value.extract::<String>()?)?,
+                "min_image_width" => config.min_image_width = value.extract()?,
+                "min_image_height" => config.min_image_height = value.extract()?,
+                "content_type_alternatives" => config.content_type_alternatives = value.extract()?,
+                "markdown_tables" => config.markdown_tables = value.extract()?,
+                "error_page_patterns" => {
+                    let patterns: Vec<String> = value.extract()?;
+                    config.error_page_patterns = patterns.iter().map(|p| p.to_lowercase()).collect();
+                }
+                "collect_warnings" => config.collect_warnings = value.extract()?,
+                "content_selectors" => {
+                    let selectors: Vec<String> = value.extract()?;
+                    config.content_selectors = selectors.into_iter()
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect();
+                }
+                "max_analysis_bytes" => config.max_analysis_bytes = value.extract()?,
+                "exclude_related_blocks" => config.exclude_related_blocks = value.extract()?,
+                "related_block_patterns" => {
+                    let patterns: Vec<String> = value.extract()?;
+                    config.related_block_patterns = patterns.iter()
+                        .map(|p| p.trim().to_lowercase())
+                        .filter(|p| !p.is_empty())
+                        .collect();
+                }
+                "now" => {
+                    let now: Option<String> = value.extract()?;
+                    config.now = now.map(|now| {
+                        DateTime::parse_from_rfc3339(now.trim())
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .map_err(|e| PyValueError::new_err(format!("now must be an RFC 3339 timestamp: {}", e)))
+                    }).transpose()?;
+                }
+                "keyword_synonyms" => {
+                    // Values may be a single synonym or a list of them
+                    let synonyms: HashMap<String, Bound<'_, PyAny>> = value.extract()?;
+                    config.keyword_synonyms = synonyms.into_iter()
+                        .map(|(term, value)| {
+                            let values: Vec<String> = match value.extract::<String>() {
+                                Ok(single) => vec![single],
+                                Err(_) => value.extract()?,
+                            };
+                            Ok((term.trim().to_lowercase(), values))
+                        })
+                        .collect::<PyResult<_>>()?;
+                }
+                "max_keyword_expansions" => config.max_keyword_expansions = value.extract()?,
+                "app_state_min_words" => config.app_state_min_words = value.extract()?,
+                "min_category_score" => config.min_category_score = value.extract()?,
+                "denied_domains" => {
+                    let domains: Vec<String> = value.extract()?;
+                    config.denied_domains = domains.iter()
+                        .map(|d| d.trim().trim_end_matches('.').to_lowercase())
+                        .filter(|d| !d.is_empty())
+                        .collect();
+                }
+                "math_placeholders" => config.math_placeholders = value.extract()?,
+                "derive_description" => config.derive_description = value.extract()?,
+                "token_counts" => config.token_counts = value.extract()?,
+                "thread_mode" => config.thread_mode = ThreadMode::parse(&value.extract::<String>()?)?,
+                "normalize_structured_data" => config.normalize_structured_data = value.extract()?,
+                "detect_low_effort" => config.detect_low_effort = value.extract()?,
+                "responsive_images" => config.responsive_images = value.extract()?,
+                "phone_numbers" => config.phone_numbers = value.extract()?,
+                "strip_encoding_errors" => config.strip_encoding_errors = value.extract()?,
+                "chunk_context_template" => config.chunk_context_template = value.extract()?,
+                "boilerplate_phrases" => {
+                    let phrases: Vec<String> = value.extract()?;
+                    config.boilerplate_phrases = phrases.iter()
+                        .map(|p| p.trim().to_lowercase())
+                        .filter(|p| !p.is_empty())
+                        .collect();
+                }
+                "max_chunk_size" => config.max_chunk_size = value.extract()?,
+                "min_chunk_size" => config.min_chunk_size = value.extract()?,
+                "overlap" => config.chunk_overlap = value.extract()?,
+                "news_sections" => {
+                    let sections: Vec<String> = value.extract()?;
+                    config.news_sections = sections.iter()
+                        .map(|s| s.trim().to_lowercase())
+                        .filter(|s| !s.is_empty())
+                        .collect();
+                }
+                "content_word_length" => config.content_word_length = value.extract()?,
+                "definitions" => config.definitions = value.extract()?,
+                "markdown_definitions" => config.markdown_definitions = value.extract()?,
+                "max_tables" => config.max_tables = value.extract()?,
+                "fenced_code" => config.fenced_code = value.extract()?,
+                "stage_budgets_ms" => config.stage_budgets_ms = value.extract()?,
+                "two_digit_year_pivot" => {
+                    let pivot: u32 = value.extract()?;
+                    if pivot > 100 {
+                        return Err(PyValueError::new_err(format!("two_digit_year_pivot must be 0..=100, got {}", pivot)));
+                    }
+                    config.two_digit_year_pivot = pivot;
+                }
+                other => {
+                    return Err(PyTypeError::new_err(format!("unknown processing option '{}'", other)));
+                }
+            }
+        }
+
+        if config.max_chunk_size == 0 || config.min_chunk_size > config.max_chunk_size {
+            return Err(PyValueError::new_err(format!(
+                "chunk sizes must satisfy min_chunk_size <= max_chunk_size and max_chunk_size > 0, got {} and {}",
+                config.min_chunk_size, config.max_chunk_size,
+            )));
+        }
+        if config.chunk_overlap >= config.max_chunk_size {
+            return Err(PyValueError::new_err(format!(
+                "overlap must be smaller than max_chunk_size ({}), got {}",
+                config.max_chunk_size, config.chunk_overlap,
+            )));
+        }
+
+        if let Some(stage) = config.stage_budgets_ms.keys().find(|stage| !BUDGETED_STAGES.contains(&stage.as_str())) {
+            return Err(PyValueError::new_err(format!(
+                "unknown stage '{}' in stage_budgets_ms, expected one of {:?}", stage, BUDGETED_STAGES,
+            )));
+        }
+
+        Ok(config)
+    }
+}
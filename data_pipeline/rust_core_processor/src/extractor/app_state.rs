@@ -0,0 +1,78 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tl::{Parser, VDom};
+
+// JSON object keys whose string values usually hold the article in app-state blobs
+const CONTENT_KEYS: &[&str] = &["articleBody", "body", "content", "text", "html", "bodyHtml", "contentHtml"];
+
+// A string shorter than this is a label or teaser, not article content
+const MIN_CONTENT_WORDS: usize = 30;
+
+// Deeply nested state trees are cut off rather than walked exhaustively
+const MAX_DEPTH: usize = 32;
+
+// `window.__INITIAL_STATE__ = {...};` style assignments in inline scripts
+static STATE_ASSIGNMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)^\s*window\.(?:__INITIAL_STATE__|__PRELOADED_STATE__|__APOLLO_STATE__|__NUXT__)\s*=\s*(.*?);?\s*$").unwrap()
+});
+static MARKUP_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+
+/// Article text recovered from client-side app state: `<script id="__NEXT_DATA__">`,
+/// `type="application/json"` blobs and `window.__INITIAL_STATE__ = {...}` assignments.
+/// Long string values under content-like keys are collected in document order, with
+/// embedded HTML stripped. None when no blob yields content.
+pub fn extract_app_state_text(dom: &VDom, parser: &Parser) -> Option<String> {
+    let mut blocks: Vec<String> = Vec::new();
+
+    for tag in dom.query_selector("script").into_iter().flatten().filter_map(|h| h.get(parser)?.as_tag()) {
+        let attrs = tag.attributes();
+        let is_json_type = attrs.get("type").flatten()
+            .map(|t| t.as_utf8_str().trim().eq_ignore_ascii_case("application/json"))
+            .unwrap_or(false);
+        let is_next_data = attrs.get("id").flatten()
+            .map(|id| id.as_utf8_str() == "__NEXT_DATA__")
+            .unwrap_or(false);
+
+        let script = tag.inner_text(parser);
+        let json_text = if is_json_type || is_next_data {
+            Some(script.as_ref())
+        } else {
+            STATE_ASSIGNMENT.captures(&script).and_then(|caps| caps.get(1)).map(|m| m.as_str())
+        };
+        // __NUXT__ is often an IIFE rather than JSON; such blobs simply fail to parse
+        let Some(state) = json_text.and_then(|text| serde_json::from_str::<serde_json::Value>(text).ok()) else {
+            continue;
+        };
+        collect_content(&state, 0, &mut blocks);
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    blocks.retain(|block| seen.insert(block.clone()));
+    if blocks.is_empty() { None } else { Some(blocks.join(" ")) }
+}
+
+fn collect_content(value: &serde_json::Value, depth: usize, blocks: &mut Vec<String>) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (key, child) in obj {
+                if let (true, Some(text)) = (CONTENT_KEYS.contains(&key.as_str()), child.as_str()) {
+                    let text = MARKUP_TAG.replace_all(text, " ").split_whitespace().collect::<Vec<_>>().join(" ");
+                    if text.split_whitespace().count() >= MIN_CONTENT_WORDS {
+                        blocks.push(text);
+                    }
+                } else {
+                    collect_content(child, depth + 1, blocks);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_content(item, depth + 1, blocks);
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,26 +1,149 @@
 use tl::{VDom, Parser, Node};
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use crate::ProcessedDocument;
 use crate::extractor::metadata_extractor;
+use crate::config::{AbbreviationMode, ProcessingConfig};
+use crate::math;
+use crate::cleaner::FastCleaner;
+use crate::types::{CodeBlock, MathBlock, TableInfo};
 
-pub struct MainContentExtractor;
+// Paragraphs shorter than this are never deduplicated (short phrases legitimately repeat)
+const MIN_DEDUPE_PARAGRAPH_WORDS: usize = 8;
 
-impl MainContentExtractor {
-    pub fn extract_main_content(&self, dom: &VDom, parser: &Parser)-> String {
-        // Priority selectors for main content
-        let content_selectors = [
+// Blocks longer than this are real prose even when they mention a boilerplate phrase
+const MAX_BOILERPLATE_BLOCK_WORDS: usize = 30;
+
+// Class/id fragments of "Read more" containers that hold the rest of the text collapsed.
+// Such tokens are exempt from the noise filter ("read-more" would otherwise match "ad").
+const EXPANDER_HINTS: &[&str] = &[
+    "read-more", "readmore", "read_more", "show-more", "showmore", "expandable",
+    "truncat", "more-content", "full-text", "fulltext",
+];
+
+// Link/button labels that reveal the rest of a truncated text
+const READ_MORE_LABELS: &[&str] = &[
+    "read more", "read more…", "read more...", "continue reading", "show more", "see more",
+    "read full story", "read the full story", "read full article", "read the full article",
+];
+
+// A collapsed container with at least this many words counts as the in-DOM continuation
+const MIN_CONTINUATION_WORDS: usize = 20;
+
+// Longer main content is not a teaser, so the read-more check skips its DOM scans
+const MAX_TEASER_WORDS: usize = 300;
+
+const NOISE_HINTS: &[&str] = &["nav", "menu", "sidebar", "footer", "header", "ad", "popup", "banner"];
+
+// Line break inside a markdown table. Extracted text is whitespace-collapsed at every
+// level (and again by FastCleaner), so rows are joined with this private-use character
+// and turned back into newlines by `restore_line_breaks` once cleaning is done.
+const LINE_BREAK: char = '\u{E000}';
+
+// One column of code indentation, carried through whitespace collapsing the same way
+const INDENT: char = '\u{E001}';
+
+// Bracket a fenced code block so `clean_outside_code` can pass it through FastCleaner
+// untouched (URLs, emails and entities in code are not noise)
+const CODE_START: char = '\u{E002}';
+const CODE_END: char = '\u{E003}';
+
+pub struct MainContentExtractor<'c> {
+    config: &'c ProcessingConfig,
+    // Normalized hashes of paragraphs already emitted; None when dedupe is off
+    seen_paragraphs: RefCell<Option<HashSet<u64>>>,
+    // Set when no content selector matched and the whole body was used
+    used_body_fallback: Cell<bool>,
+    // Inner HTML of the regions the last extraction used; filled when include_content_html is on
+    region_html: RefCell<String>,
+    // Page math, for "[math:N]" placeholders when config.math_placeholders is on
+    math_blocks: &'c [MathBlock],
+}
+
+impl<'c> MainContentExtractor<'c> {
+    pub fn new(config: &'c ProcessingConfig) -> Self {
+        Self {
+            config,
+            seen_paragraphs: RefCell::new(config.dedupe_paragraphs.then(HashSet::new)),
+            used_body_fallback: Cell::new(false),
+            region_html: RefCell::new(String::new()),
+            math_blocks: &[],
+        }
+    }
+
+    /// Math blocks that placeholders in main_content index into
+    pub fn with_math_blocks(mut self, math_blocks: &'c [MathBlock]) -> Self {
+        self.math_blocks = math_blocks;
+        self
+    }
+
+    /// True if the last extraction fell back to the entire body
+    pub fn used_body_fallback(&self) -> bool {
+        self.used_body_fallback.get()
+    }
+
+    /// Serialized inner HTML of the region(s) the last extraction selected, in the
+    /// same order their text was appended; None unless include_content_html is on
+    pub fn region_html(&self) -> Option<String> {
+        self.config.include_content_html.then(|| self.region_html.borrow().trim().to_string())
+    }
+
+    fn push_region_html(&self, node: &Node, parser: &Parser) {
+        if self.config.include_content_html {
+            let mut region_html = self.region_html.borrow_mut();
+            region_html.push_str(&node.inner_html(parser));
+            region_html.push('\n');
+        }
+    }
+
+    /// Extract main content plus the (start, end) char offsets of the selected
+    /// region within the flattened body text, so callers can trim or extend it
+    pub fn extract_main_content_with_region(&self, dom: &VDom, parser: &Parser) -> (String, Option<(usize, usize)>) {
+        // Priority selectors for main content; caller-registered containers (custom
+        // elements such as <app-article-body>) are tried first
+        let builtin_selectors = [
             "main", "article", ".content", ".post-content", ".entry-content",
             "#content", ".article-body", ".post-body", ".article-text",
             "[role='main']", ".main-content", ".page-content", ".content-wrapper",
             ".story-content", ".article-wrapper", ".text-content"
         ];
+        let content_selectors: Vec<&str> = self.config.content_selectors.iter()
+            .map(String::as_str)
+            .chain(builtin_selectors)
+            .collect();
+
+        let body_node = dom.query_selector("body")
+            .and_then(|mut iter| iter.next())
+            .and_then(|body_node| body_node.get(parser));
+        let body_text = body_node
+            .map(|node| self.extract_text_without_dedupe(node, parser))
+            .unwrap_or_default();
 
         // Try each selector and append all meaningful content
         let mut main_text = String::new();
+        let mut region: Option<(usize, usize)> = None;
         for selector in &content_selectors {
             if let Some(content_node) = dom.query_selector(selector).and_then(|mut iter| iter.next()) {
                 if let Some(node) = content_node.get(parser) {
                     let content = self.extract_clean_text_from_node(node, parser);
                     if content.trim().len() > 50 {
+                        // Locate the region by its full text, even if dedupe dropped paragraphs
+                        let region_text = if self.config.dedupe_paragraphs {
+                            self.extract_text_without_dedupe(node, parser)
+                        } else {
+                            content.clone()
+                        };
+                        if let Some(byte_start) = body_text.find(region_text.trim()) {
+                            let start = body_text[..byte_start].chars().count();
+                            let end = start + region_text.trim().chars().count();
+                            region = Some(match region {
+                                Some((s, e)) => (s.min(start), e.max(end)),
+                                None => (start, end),
+                            });
+                        }
+                        self.push_region_html(node, parser);
                         main_text.push_str(&content);
                         main_text.push(' ');
                     }
@@ -30,22 +153,215 @@ impl MainContentExtractor {
 
         // Fallback: entire body
         if main_text.trim().is_empty() {
-            if let Some(body_node) = dom.query_selector("body").and_then(|mut iter| iter.next()) {
-                if let Some(node) = body_node.get(parser) {
+            self.used_body_fallback.set(true);
+            if let Some(node) = body_node {
+                self.push_region_html(node, parser);
+            }
+            match body_node {
+                Some(node) if self.config.dedupe_paragraphs => {
                     main_text.push_str(&self.extract_clean_text_from_node(node, parser));
                 }
+                _ => main_text.push_str(&body_text),
+            }
+            if !body_text.is_empty() {
+                region = Some((0, body_text.chars().count()));
             }
         }
 
-        main_text.trim().to_string()
+        (main_text.trim().to_string(), region)
+    }
+
+    /// Extract text with paragraph dedupe suspended, leaving the seen set untouched
+    fn extract_text_without_dedupe(&self, node: &Node, parser: &Parser) -> String {
+        let saved = self.seen_paragraphs.borrow_mut().take();
+        let text = self.extract_clean_text_from_node(node, parser);
+        *self.seen_paragraphs.borrow_mut() = saved;
+        text
+    }
+
+    /// True if an identical (after case/punctuation/whitespace normalization)
+    /// paragraph was already emitted; records the paragraph otherwise
+    fn is_repeated_paragraph(&self, paragraph: &str) -> bool {
+        let mut seen = self.seen_paragraphs.borrow_mut();
+        let Some(seen) = seen.as_mut() else {
+            return false;
+        };
+
+        let words: Vec<String> = paragraph
+            .split_whitespace()
+            .map(|w| w.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+        if words.len() < MIN_DEDUPE_PARAGRAPH_WORDS {
+            return false;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        words.hash(&mut hasher);
+        !seen.insert(hasher.finish())
+    }
+
+    /// Text of an emphasized span wrapped in `marker`. Short runs are kept (emphasis is
+    /// usually a few words) and nested emphasis only adds markers of a different kind,
+    /// so <strong><b>x</b></strong> stays **x** while <strong><em>x</em></strong> is ***x***.
+    fn extract_emphasis_text(&self, node: &Node, parser: &Parser, marker: &str) -> String {
+        fn collect(node: &Node, parser: &Parser, outer: &str, out: &mut String) {
+            match node {
+                Node::Tag(tag) => {
+                    let tag_name = tag.name().as_utf8_str().to_lowercase();
+                    if matches!(tag_name.as_str(), "script" | "style" | "noscript") {
+                        return;
+                    }
+                    let inner_marker = emphasis_marker(&tag_name).filter(|m| *m != outer);
+                    let mut inner = String::new();
+                    for child in tag.children().top().iter() {
+                        if let Some(child_node) = child.get(parser) {
+                            collect(child_node, parser, inner_marker.unwrap_or(outer), &mut inner);
+                        }
+                    }
+                    let inner = inner.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if inner.is_empty() {
+                        return;
+                    }
+                    out.push(' ');
+                    match inner_marker {
+                        Some(m) => out.push_str(&format!("{}{}{}", m, inner, m)),
+                        None => out.push_str(&inner),
+                    }
+                    out.push(' ');
+                }
+                Node::Raw(text) => {
+                    out.push_str(&text.as_utf8_str().replace("\u{200b}", "").replace("&nbsp;", " "));
+                }
+                Node::Comment(_) => {}
+            }
+        }
+
+        let Node::Tag(tag) = node else {
+            return String::new();
+        };
+        let mut inner = String::new();
+        for child in tag.children().top().iter() {
+            if let Some(child_node) = child.get(parser) {
+                collect(child_node, parser, marker, &mut inner);
+            }
+        }
+
+        let inner = inner.split_whitespace().collect::<Vec<_>>().join(" ");
+        if inner.is_empty() {
+            String::new()
+        } else {
+            format!("{}{}{}", marker, inner, marker)
+        }
+    }
+
+    /// Short text carrying a configured boilerplate phrase ("privacy policy", "all rights
+    /// reserved", ...), e.g. a footer line that escaped the footer tag/class filters
+    fn is_boilerplate_block(&self, text: &str) -> bool {
+        if self.config.boilerplate_phrases.is_empty() || text.split_whitespace().count() > MAX_BOILERPLATE_BLOCK_WORDS {
+            return false;
+        }
+        let lower = text.to_lowercase();
+        self.config.boilerplate_phrases.iter().any(|phrase| lower.contains(phrase.as_str()))
+    }
+
+    /// True for recommendation widgets ("related-posts", "you-may-like"). The article
+    /// itself is never treated as one: <article>, <main> and role="main" are exempt.
+    fn is_related_block(&self, tag: &tl::HTMLTag, tag_name: &str) -> bool {
+        let attrs = tag.attributes();
+        let is_main_region = matches!(tag_name, "article" | "main" | "body")
+            || attrs.get("role").flatten().map(|r| r.as_utf8_str().eq_ignore_ascii_case("main")).unwrap_or(false);
+        if is_main_region {
+            return false;
+        }
+
+        // Compare whole hyphen/underscore-separated parts, so "you-may-like" matches
+        // "you_may_like-box" and "related" does not match "unrelated"
+        let matches_pattern = |value: &str| {
+            value.to_lowercase().split_whitespace().any(|token| {
+                let parts: Vec<&str> = token.split(['-', '_']).collect();
+                self.config.related_block_patterns.iter().any(|pattern| {
+                    let wanted: Vec<&str> = pattern.split(['-', '_']).collect();
+                    parts.windows(wanted.len()).any(|window| window == wanted.as_slice())
+                })
+            })
+        };
+
+        ["class", "id"].iter()
+            .filter_map(|name| attrs.get(*name).flatten())
+            .any(|value| matches_pattern(&value.as_utf8_str()))
+    }
+
+    /// GitHub-flavored markdown for a <table>: the first row is the header, ragged rows are
+    /// padded with empty cells and "|" inside cells is escaped
+    fn render_markdown_table(table: &tl::HTMLTag, parser: &Parser) -> String {
+        let rows: Vec<Vec<String>> = table_rows(table, parser).into_iter()
+            .map(|(_, cells)| cells.into_iter().map(|cell| cell.replace('|', "\\|")).collect())
+            .collect();
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return String::new();
+        }
+
+        let format_row = |cells: &[String]| {
+            let padded = (0..columns).map(|i| cells.get(i).map(String::as_str).unwrap_or(""));
+            format!("| {} |", padded.collect::<Vec<_>>().join(" | "))
+        };
+        let mut lines = vec![format_row(&rows[0]), format_row(&vec!["---".to_string(); columns])];
+        lines.extend(rows[1..].iter().map(|row| format_row(row)));
+
+        let separator = format!(" {} ", LINE_BREAK);
+        format!("{}{}{}", separator, lines.join(&separator), separator)
+    }
+
+    /// A <dl> as one "**term**: definition" line per pair, set apart like markdown tables
+    fn render_markdown_definitions(list: &tl::HTMLTag, parser: &Parser) -> String {
+        let lines: Vec<String> = definition_pairs(list, parser).into_iter()
+            .map(|(term, definition)| format!("**{}**: {}", term, definition))
+            .collect();
+        if lines.is_empty() {
+            return String::new();
+        }
+        let separator = format!(" {} ", LINE_BREAK);
+        format!("{}{}{}", separator, lines.join(&separator), separator)
+    }
+
+    /// A code block as a ```-fenced block, one line-break placeholder per source line.
+    /// Blank lines inside the block become bare separators; leading and trailing ones are dropped.
+    fn render_fenced_code(block: &CodeBlock) -> String {
+        let separator = format!(" {} ", LINE_BREAK);
+        let lines: Vec<String> = block.code.trim_matches(['\r', '\n']).lines()
+            .map(|line| {
+                let body = line.trim_start();
+                if body.is_empty() {
+                    return String::new();
+                }
+                let indent: String = line[..line.len() - body.len()].chars()
+                    .flat_map(|c| std::iter::repeat_n(INDENT, if c == '\t' { 4 } else { 1 }))
+                    .collect();
+                format!("{}{}", indent, body.trim_end())
+            })
+            .collect();
+        format!(
+            "{}{}```{}{}{}{}```{}{}",
+            separator, CODE_START, block.language, separator, lines.join(&separator), separator, CODE_END, separator,
+        )
     }
 
     fn extract_clean_text_from_node(&self, node: &Node, parser: &Parser) -> String {
         let mut clean_text = String::new();
+        let mut is_quote = false;
 
         match node {
             Node::Tag(tag) => {
                 let tag_name = tag.name().as_utf8_str().to_lowercase();
+                is_quote = tag_name == "blockquote" && self.config.quote_markers;
+                if self.config.math_placeholders {
+                    if let Some(index) = math::element_math(node, parser).and_then(|m| math::block_index(self.math_blocks, &m.source)) {
+                        return math::placeholder(index);
+                    }
+                }
                 if matches!(tag_name.as_str(), 
                     "script" | "style" | "noscript" | "nav" | "header" | "footer" |
                     "aside" | "menu" | "menuitem" | "figure" | "figcaption" |
@@ -54,18 +370,43 @@ impl MainContentExtractor {
                     return String::new();
                 }
 
+                // Hidden or collapsed (aria-hidden, "read more") content that is in the DOM is kept
                 let attrs = tag.attributes();
-                if let Some(class_val) = attrs.get("class").flatten() {
-                    let class_str = class_val.as_utf8_str().to_lowercase();
-                    if ["nav","menu","sidebar","footer","header","ad","popup","banner"].iter().any(|n| class_str.contains(n)) {
-                        return String::new();
+                if ["class", "id"].iter().any(|name| {
+                    attrs.get(*name).flatten().is_some_and(|value| {
+                        value.as_utf8_str().to_lowercase().split_whitespace()
+                            .filter(|token| !is_expander_token(token))
+                            .any(|token| NOISE_HINTS.iter().any(|n| token.contains(n)))
+                    })
+                }) {
+                    return String::new();
+                }
+
+                if self.config.exclude_related_blocks && self.is_related_block(tag, &tag_name) {
+                    return String::new();
+                }
+
+                if self.config.abbreviations == AbbreviationMode::Inline && matches!(tag_name.as_str(), "abbr" | "acronym") {
+                    if let Some(expanded) = abbreviation_expansion(node, parser).map(|(abbr, title)| format!("{} ({})", abbr, title)) {
+                        return expanded;
                     }
                 }
 
-                if let Some(id_val) = attrs.get("id").flatten() {
-                    let id_str = id_val.as_utf8_str().to_lowercase();
-                    if ["nav","menu","sidebar","footer","header","ad","popup","banner"].iter().any(|n| id_str.contains(n)) {
-                        return String::new();
+                if tag_name == "dl" && self.config.markdown_definitions {
+                    return Self::render_markdown_definitions(tag, parser);
+                }
+
+                if tag_name == "pre" && self.config.fenced_code {
+                    return code_block(tag, parser).map(|block| Self::render_fenced_code(&block)).unwrap_or_default();
+                }
+
+                if tag_name == "table" && self.config.markdown_tables {
+                    return Self::render_markdown_table(tag, parser);
+                }
+
+                if self.config.emphasis_markers {
+                    if let Some(marker) = emphasis_marker(&tag_name) {
+                        return self.extract_emphasis_text(node, parser, marker);
                     }
                 }
 
@@ -84,7 +425,7 @@ impl MainContentExtractor {
                                      .replace("\r", " ")
                                      .trim()
                                      .to_string();
-                if !txt_cleaned.is_empty() && txt_cleaned.len() > 20 {
+                if !txt_cleaned.is_empty() && txt_cleaned.len() > 20 && !self.is_repeated_paragraph(&txt_cleaned) {
                     clean_text.push_str(&txt_cleaned);
                     clean_text.push(' ');
                 }
@@ -93,6 +434,267 @@ impl MainContentExtractor {
             // _ => {} // unreachable, all Node variants handled above
         }
 
-        clean_text.split_whitespace().collect::<Vec<_>>().join(" ")
+        let clean_text = clean_text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if self.is_boilerplate_block(&clean_text) {
+            return String::new();
+        }
+        if is_quote && !clean_text.is_empty() {
+            format!("> {}", clean_text)
+        } else {
+            clean_text
+        }
+    }
+}
+
+fn is_expander_token(token: &str) -> bool {
+    EXPANDER_HINTS.iter().any(|hint| token.contains(hint))
+}
+
+/// True when the page shows a "Read more" link or button, main_content is a teaser (at
+/// most MAX_TEASER_WORDS words) ending on an ellipsis, and no collapsed or hidden container
+/// holds the rest: the continuation is loaded by script and was not extracted. The DOM is
+/// only scanned once the cheap text checks pass.
+pub fn read_more_without_continuation(dom: &VDom, parser: &Parser, main_content: &str) -> bool {
+    let main_content = main_content.trim_end();
+    if !(main_content.ends_with('…') || main_content.ends_with("..."))
+        || main_content.split_whitespace().count() > MAX_TEASER_WORDS
+    {
+        return false;
+    }
+    let has_trigger = dom.query_selector("a, button").into_iter().flatten()
+        .filter_map(|handle| handle.get(parser))
+        .any(|node| READ_MORE_LABELS.contains(&node.inner_text(parser).trim().to_lowercase().as_str()));
+    if !has_trigger {
+        return false;
+    }
+    !dom.nodes().iter().filter_map(|node| node.as_tag()).any(|tag| {
+        let attrs = tag.attributes();
+        let collapsed = attrs.contains("hidden")
+            || attrs.get("aria-hidden").flatten().is_some_and(|v| v.as_utf8_str().trim() == "true")
+            || ["class", "id"].iter().any(|name| attrs.get(*name).flatten().is_some_and(|value| {
+                value.as_utf8_str().to_lowercase().split_whitespace().any(is_expander_token)
+            }));
+        collapsed && tag.inner_text(parser).split_whitespace().count() >= MIN_CONTINUATION_WORDS
+    })
+}
+
+/// Markdown-style marker for an inline emphasis tag
+fn emphasis_marker(tag_name: &str) -> Option<&'static str> {
+    match tag_name {
+        "strong" | "b" => Some("**"),
+        "em" | "i" => Some("*"),
+        _ => None,
+    }
+}
+
+/// (abbreviation, expansion) of an <abbr>/<acronym> with a title that actually expands it
+pub fn abbreviation_expansion(node: &Node, parser: &Parser) -> Option<(String, String)> {
+    let title = node.as_tag()?.attributes().get("title").flatten()?.as_utf8_str();
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    let abbr = node.inner_text(parser).split_whitespace().collect::<Vec<_>>().join(" ");
+    if abbr.is_empty() || title.is_empty() || title.eq_ignore_ascii_case(&abbr) {
+        return None;
+    }
+    Some((abbr, title))
+}
+
+/// Source of a <pre> block (its <code> child when there is one), entity-decoded with
+/// whitespace and newlines kept. The language comes from a "language-x" / "lang-x" class
+/// or a data-lang / data-language attribute on either element, else "". None when empty.
+pub fn code_block(pre: &tl::HTMLTag, parser: &Parser) -> Option<CodeBlock> {
+    let code = pre.query_selector(parser, "code")
+        .and_then(|mut iter| iter.next())
+        .and_then(|handle| handle.get(parser).and_then(|n| n.as_tag()));
+    let language = [Some(pre), code].into_iter().flatten()
+        .find_map(|tag| {
+            let attrs = tag.attributes();
+            let from_class = attrs.get("class").flatten().and_then(|class| {
+                class.as_utf8_str().split_whitespace()
+                    .find_map(|token| token.strip_prefix("language-").or_else(|| token.strip_prefix("lang-")).map(str::to_string))
+            });
+            from_class.or_else(|| ["data-lang", "data-language"].iter()
+                .find_map(|name| attrs.get(*name).flatten().map(|v| v.as_utf8_str().trim().to_string())))
+        })
+        .map(|language| language.to_lowercase())
+        .unwrap_or_default();
+    let source = code.unwrap_or(pre).inner_text(parser);
+    let source = FastCleaner::decode_entities(&source);
+    let source = source.trim_matches(['\n', '\r']).trim_end();
+    (!source.trim().is_empty()).then(|| CodeBlock { language, code: source.to_string() })
+}
+
+// Rows of a table as (all cells are <th>, cell texts), skipping rows without cells
+fn table_rows(table: &tl::HTMLTag, parser: &Parser) -> Vec<(bool, Vec<String>)> {
+    table.query_selector(parser, "tr")
+        .into_iter()
+        .flatten()
+        .filter_map(|handle| handle.get(parser).and_then(|n| n.as_tag()))
+        .map(|row| {
+            let cells: Vec<&tl::HTMLTag> = row.children().top().iter()
+                .filter_map(|child| child.get(parser).and_then(|n| n.as_tag()))
+                .filter(|cell| matches!(cell.name().as_utf8_str().to_lowercase().as_str(), "td" | "th"))
+                .collect();
+            let all_header = cells.iter().all(|cell| cell.name().as_utf8_str().eq_ignore_ascii_case("th"));
+            let texts: Vec<String> = cells.iter()
+                .map(|cell| cell.inner_text(parser).replace("&nbsp;", " ").split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect();
+            (all_header, texts)
+        })
+        .filter(|(_, cells)| !cells.is_empty())
+        .collect()
+}
+
+/// A <table> as headers plus data rows. Headers are the leading all-<th> row (thead or
+/// the first row); None for layout tables that never have more than one column.
+pub fn table_info(table: &tl::HTMLTag, parser: &Parser) -> Option<TableInfo> {
+    let mut rows = table_rows(table, parser);
+    if rows.iter().map(|(_, cells)| cells.len()).max().unwrap_or(0) < 2 {
+        return None;
+    }
+    let headers = match rows.first() {
+        Some((true, _)) => rows.remove(0).1,
+        _ => Vec::new(),
+    };
+    let caption = table.query_selector(parser, "caption")
+        .and_then(|mut iter| iter.next())
+        .and_then(|handle| handle.get(parser))
+        .map(|node| FastCleaner::normalize_value_text(&node.inner_text(parser)))
+        .unwrap_or_default();
+    Some(TableInfo { headers, rows: rows.into_iter().map(|(_, cells)| cells).collect(), caption })
+}
+
+/// (term, definition) pairs of a <dl> in order. Consecutive <dt>s share the <dd> that
+/// follows them, several <dd>s of one term are joined (with "; " unless one ends a
+/// sentence), and <div> groups are looked through. Text is entity-decoded and
+/// whitespace-collapsed.
+pub fn definition_pairs(list: &tl::HTMLTag, parser: &Parser) -> Vec<(String, String)> {
+    fn collect(tag: &tl::HTMLTag, parser: &Parser, items: &mut Vec<(bool, String)>) {
+        for child in tag.children().top().iter().filter_map(|c| c.get(parser).and_then(|n| n.as_tag())) {
+            match child.name().as_utf8_str().to_lowercase().as_str() {
+                "dt" => items.push((true, FastCleaner::normalize_value_text(&child.inner_text(parser)))),
+                "dd" => items.push((false, FastCleaner::normalize_value_text(&child.inner_text(parser)))),
+                "div" => collect(child, parser, items),
+                _ => {}
+            }
+        }
+    }
+    let mut items = Vec::new();
+    collect(list, parser, &mut items);
+
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    let mut terms: Vec<String> = Vec::new();
+    let mut defined = 0; // Pairs at the end of `pairs` belonging to the current terms
+    for (is_term, text) in items.into_iter().filter(|(_, text)| !text.is_empty()) {
+        if is_term {
+            if defined > 0 {
+                terms.clear();
+                defined = 0;
+            }
+            terms.push(text);
+        } else if defined > 0 {
+            let start = pairs.len() - defined;
+            for (_, definition) in &mut pairs[start..] {
+                definition.push_str(if definition.ends_with(['.', ';', '!', '?']) { " " } else { "; " });
+                definition.push_str(&text);
+            }
+        } else {
+            pairs.extend(terms.iter().map(|term| (term.clone(), text.clone())));
+            defined = terms.len();
+        }
+    }
+    pairs
+}
+
+/// Turn the line-break placeholders of markdown tables into real newlines (and code
+/// indentation placeholders back into spaces). Empty lines are dropped except inside
+/// fenced code, where they are part of the source.
+pub fn restore_line_breaks(text: &str) -> String {
+    if !text.contains([LINE_BREAK, CODE_START, CODE_END]) {
+        return text.to_string();
+    }
+    let mut in_code = false;
+    let mut lines = Vec::new();
+    for raw in text.split(LINE_BREAK) {
+        let opens = raw.contains(CODE_START);
+        let closes = raw.contains(CODE_END);
+        let line = raw.replace([CODE_START, CODE_END], "").trim().replace(INDENT, " ");
+        if !line.is_empty() || (in_code && !opens && !closes) {
+            lines.push(line);
+        }
+        if opens {
+            in_code = true;
+        }
+        if closes {
+            in_code = false;
+        }
+    }
+    lines.join("\n")
+}
+
+/// Apply `clean` to the prose of `text` only; fenced code blocks are kept verbatim. A chunk
+/// may start inside a block, which shows as a closing bracket before any opening one.
+pub fn clean_outside_code(text: &str, clean: impl Fn(&str) -> String) -> String {
+    let Some(first) = text.find([CODE_START, CODE_END]) else { return clean(text) };
+    let mut in_code = text[first..].starts_with(CODE_END);
+    let mut parts = Vec::new();
+    let mut rest = text;
+    loop {
+        let (segment, next) = match rest.find(if in_code { CODE_END } else { CODE_START }) {
+            Some(pos) => {
+                let marker = if in_code { CODE_END } else { CODE_START };
+                (&rest[..pos], Some(pos + marker.len_utf8()))
+            }
+            None => (rest, None),
+        };
+        let part = if in_code {
+            format!("{}{}{}", CODE_START, segment.trim(), CODE_END)
+        } else {
+            clean(segment)
+        };
+        if !part.trim().is_empty() {
+            parts.push(part);
+        }
+        match next {
+            Some(end) => {
+                rest = &rest[end..];
+                in_code = !in_code;
+            }
+            None => break,
+        }
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fenced_code_keeps_blank_lines_and_indentation() {
+        let block = CodeBlock {
+            language: "python".to_string(),
+            code: "def a():\n    x = 1\n\n    return x\n".to_string(),
+        };
+        let rendered = MainContentExtractor::render_fenced_code(&block);
+        assert_eq!(
+            restore_line_breaks(&format!("Intro {} outro", rendered)),
+            "Intro\n```python\ndef a():\n    x = 1\n\n    return x\n```\noutro",
+        );
+    }
+
+    #[test]
+    fn collapsed_read_more_section_is_extracted() {
+        let html = r#"<html><body><article>
+            <p>The council approved the new cycle lanes after a long debate on Tuesday evening...</p>
+            <div class="collapse read-more-content" aria-hidden="true">
+                <p>Work starts in spring on the riverside route, with the school streets following in autumn once consultation closes.</p>
+                <p>Residents can comment on the detailed plans at the library until the end of the month.</p>
+            </div>
+            <button>Read more</button>
+        </article></body></html>"#;
+        let document = crate::extractor::extract_all_metadata(html, "https://example.com/news").unwrap();
+        assert!(document.main_content.contains("Work starts in spring"), "{}", document.main_content);
+        assert!(document.main_content.contains("until the end of the month"));
+        assert!(!document.read_more_truncated);
     }
 }
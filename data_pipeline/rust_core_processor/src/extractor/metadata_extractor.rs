@@ -1,12 +1,188 @@
+    use std::cell::RefCell;
     use std::collections::{HashMap, HashSet};
     use regex::Regex;
     use rust_stemmers::{Algorithm, Stemmer};
-    use crate::types::ImageInfo;
+    use crate::types::{ContentTypeCandidate, GeoInfo, HowToData, HowToStep, ImageInfo, MediaObject, ProductData, RecipeData, ResponsiveImage, SrcsetCandidate, ThreadPost};
+    use crate::config::{ProcessingConfig, StageTimer};
+    use crate::cleaner::FastCleaner;
     use tl::parse;
     use tl::ParserOptions;   
     use once_cell::sync::Lazy;
 
 
+    // schema.org NutritionInformation properties, found as microdata itemprops
+    const NUTRITION_PROPERTIES: &[&str] = &[
+        "calories", "carbohydrateContent", "cholesterolContent", "fatContent", "fiberContent",
+        "proteinContent", "saturatedFatContent", "servingSize", "sodiumContent", "sugarContent",
+        "transFatContent", "unsaturatedFatContent",
+    ];
+
+    // JSON-LD types the pipeline reads; blocks mentioning them are parsed first under the cap
+    const PRIORITY_JSON_LD_TYPES: &[&str] = &[
+        "\"Article\"", "\"NewsArticle\"", "\"BlogPosting\"", "\"Product\"", "\"Recipe\"", "\"VideoObject\"",
+    ];
+
+    // (content type, title/description keywords, URL keywords) in classification precedence
+    const CONTENT_TYPE_RULES: &[(&str, &[&str], &[&str])] = &[
+        ("faq", &["faq", "question", "answer", "help"], &[]),
+        ("product", &["buy", "price", "product", "cart", "shop", "add to basket"], &[]),
+        ("video", &["video", "watch", "youtube"], &["video"]),
+        ("recipe", &["recipe", "ingredients", "cook", "servings"], &[]),
+        ("review", &["review", "rating", "stars"], &[]),
+        ("blog", &["blog", "news", "article", "post", "journal", "press", "update", "editorial"], &["blog", "news", "post"]),
+    ];
+
+    // schema.org itemtypes (lowercased) that name a kind of page, as opposed to site parts
+    // (WPHeader, BreadcrumbList) or entities on it (Organization, Person)
+    const MICRODATA_CONTENT_TYPES: &[&str] = &[
+        "article", "newsarticle", "blogposting", "techarticle", "scholarlyarticle", "report",
+        "product", "recipe", "videoobject", "faqpage", "qapage", "question",
+        "discussionforumposting", "howto", "review", "event", "course", "jobposting",
+    ];
+
+    // og:type values taken as content types; "video.movie", "music.song" etc. count as
+    // their family, and "website" says nothing
+    const OG_CONTENT_TYPES: &[&str] = &["article", "book", "profile", "product", "video", "music"];
+
+    // Common spellings of newspaper sections, mapped onto the default vocabulary
+    const NEWS_SECTION_ALIASES: &[(&str, &str)] = &[
+        ("tech", "technology"), ("sport", "sports"), ("finance", "business"), ("money", "business"),
+        ("markets", "business"), ("comment", "opinion"), ("opinions", "opinion"),
+        ("commentisfree", "opinion"), ("world news", "world"), ("international", "world"),
+        ("arts", "culture"), ("life", "lifestyle"), ("climate", "environment"),
+    ];
+
+    // Breadcrumb trails; links are read inside each container (tl has no descendant selectors)
+    const BREADCRUMB_SELECTORS: &str = ".breadcrumb, .breadcrumbs, [aria-label='breadcrumb'], [aria-label='Breadcrumb']";
+
+    // Price symbols mapped to ISO 4217; prefixed dollars before the bare "$"
+    const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+        ("US$", "USD"), ("C$", "CAD"), ("A$", "AUD"), ("R$", "BRL"), ("$", "USD"), ("€", "EUR"),
+        ("£", "GBP"), ("¥", "JPY"), ("₹", "INR"), ("₩", "KRW"), ("₽", "RUB"), ("₺", "TRY"),
+    ];
+    // ISO 4217 codes recognized when written out in price text ("19.99 USD")
+    const CURRENCY_CODES: &[&str] = &[
+        "USD", "EUR", "GBP", "JPY", "CNY", "INR", "CAD", "AUD", "NZD", "CHF", "SEK", "NOK",
+        "DKK", "PLN", "CZK", "HUF", "RON", "BGN", "TRY", "RUB", "UAH", "BRL", "MXN", "ARS",
+        "CLP", "COP", "PEN", "ZAR", "NGN", "KES", "EGP", "MAD", "AED", "SAR", "QAR", "KWD",
+        "BHD", "OMR", "ILS", "KRW", "HKD", "TWD", "SGD", "MYR", "THB", "IDR", "PHP", "VND",
+        "PKR", "BDT",
+    ];
+
+    // Author boxes and bylines that may carry a bio paragraph and profile links
+    const AUTHOR_BOX_SELECTORS: &str = ".author-bio, .author-box, .about-author, .author-info, .author-card, .author-profile, .byline, .author";
+    const AUTHOR_SOCIAL_HOSTS: &[&str] = &[
+        "twitter.com", "x.com", "linkedin.com", "facebook.com", "instagram.com", "github.com",
+        "mastodon.social", "threads.net", "bsky.app", "youtube.com",
+    ];
+    // A shorter paragraph in an author box is a name or a date line, not a bio
+    const MIN_AUTHOR_BIO_WORDS: usize = 8;
+
+    // Post containers of common forum / Q&A engines (Stack Exchange, Discourse, phpBB-likes)
+    const THREAD_POST_SELECTORS: &str = ".question, .answer, .topic-post, .forum-post, .thread-post";
+    const THREAD_BODY_SELECTORS: &str = ".s-prose, .js-post-body, .post-text, .cooked, .postbody, .post-content, .message-body";
+    const THREAD_AUTHOR_SELECTORS: &str = ".username, .author, .post-author, [itemprop='author']";
+
+    static WORD_TOKEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\w+\b").unwrap());
+
+    // Folded keyword and its weight towards the category score
+    type CategoryKeyword = (String, usize);
+
+    // Weighted category keywords, case-folded once the same way as the text they are matched against
+    static CATEGORY_VOCABULARY: Lazy<Vec<(&str, Vec<CategoryKeyword>)>> = Lazy::new(|| {
+        let category_keywords: Vec<(&str, Vec<&str>)> = vec![
+            ("news", vec![
+                "news", "breaking", "update", "report", "headline", "journal", "media", "press",
+                "announcement", "current", "daily news", "broadcast", "bulletin", "article", "coverage"
+            ]),
+            ("sports", vec![
+                "football", "soccer", "basketball", "tennis", "cricket", "match", "tournament", "goal",
+                "score", "league", "athlete", "olympics", "championship", "competition", "playoff",
+                "coach", "team", "game", "sportsmanship", "player"
+            ]),
+            ("finance", vec![
+                "stocks", "market", "investment", "finance", "economy", "bitcoin", "trading", "crypto",
+                "banking", "fund", "portfolio", "mutual fund", "currency", "inflation", "deficit",
+                "revenue", "capital", "dividend", "savings", "insurance"
+            ]),
+            ("health", vec![
+                "health", "medicine", "wellness", "fitness", "disease", "nutrition", "exercise",
+                "mental health", "medical", "therapy", "diet", "treatment", "hospital", "doctor",
+                "clinic", "vaccine", "infection", "immune", "prevention", "rehabilitation"
+            ]),
+            ("entertainment", vec![
+                "movie", "film", "tv", "music", "celebrity", "show", "concert", "series", "album",
+                "entertainment", "theater", "drama", "comedy", "festival", "artist", "actor", "actress",
+                "performance", "pop culture"
+            ]),
+            ("science", vec![
+                "research", "experiment", "physics", "chemistry", "biology", "scientist", "study",
+                "discovery", "laboratory", "experiment", "theory", "analysis", "observation",
+                "scientific", "innovation", "space", "astronomy", "genetics", "geology", "climate"
+            ]),
+            ("travel", vec![
+                "travel", "tourism", "destination", "flight", "hotel", "journey", "adventure", "trip",
+                "vacation", "holiday", "explore", "sightseeing", "cruise", "itinerary", "backpacking",
+                "resort", "beach", "mountain", "culture", "transportation"
+            ]),
+            ("food", vec![
+                "food", "cuisine", "recipe", "dish", "restaurant", "meal", "dining", "chef",
+                "ingredient", "gourmet", "taste", "baking", "cooking", "snack", "drink",
+                "beverage", "dessert", "nutrition", "vegan", "organic"
+            ]),
+            ("fashion", vec![
+                "fashion", "style", "clothing", "apparel", "designer", "trend", "runway",
+                "collection", "brand", "outfit", "accessory", "model", "vogue", "couture",
+                "textile", "footwear", "jewelry", "cosmetics", "hairstyle", "makeup"
+            ]),
+            ("education", vec![
+                "education", "learning", "school", "college", "university", "course",
+                "student", "teacher", "lecture", "curriculum", "study", "training",
+                "knowledge", "academy", "classroom", "exam", "scholarship", "tutorial", "online course", "degree"
+            ])
+        ];
+        category_keywords
+            .into_iter()
+            .map(|(category, keywords)| {
+                let folded = keywords.iter()
+                    .map(|kw| (FastCleaner::fold_case(kw), if kw.contains(' ') { 2 } else { 1 }))
+                    .collect();
+                (category, folded)
+            })
+            .collect()
+    });
+
+    // The category vocabulary stemmed with one language's algorithm, so text stemmed the
+    // same way lines up with it
+    struct CategoryStemmer {
+        algorithm: Algorithm,
+        stemmer: Stemmer,
+        vocabulary: Vec<(&'static str, Vec<CategoryKeyword>)>,
+    }
+
+    impl CategoryStemmer {
+        fn new(algorithm: Algorithm) -> Self {
+            let stemmer = Stemmer::create(algorithm);
+            let vocabulary = CATEGORY_VOCABULARY.iter()
+                .map(|(category, keywords)| {
+                    let stemmed = keywords.iter()
+                        .map(|(kw, weight)| {
+                            let words: Vec<String> = kw.split(' ').map(|word| stemmer.stem(word).to_string()).collect();
+                            (words.join(" "), *weight)
+                        })
+                        .collect();
+                    (*category, stemmed)
+                })
+                .collect();
+            Self { algorithm, stemmer, vocabulary }
+        }
+    }
+
+    thread_local! {
+        // Stemmer of the last categorised document's language; batches are mostly one language
+        static CATEGORY_STEMMER: RefCell<CategoryStemmer> = RefCell::new(CategoryStemmer::new(Algorithm::English));
+    }
+
     pub struct MetadataExtractor<'a> {
         dom: &'a tl::VDom<'a>,
         parser: &'a tl::Parser<'a>,
@@ -21,6 +197,15 @@
         time_nodes: Vec<tl::NodeHandle>,
         author_nodes: Vec<tl::NodeHandle>,
         canonical_node: Option<tl::NodeHandle>,
+        max_json_ld_blocks: usize,
+        max_json_ld_bytes: usize,
+        json_ld_skipped: usize,
+        json_ld_failed: usize,
+        structured_data_budget: Option<std::time::Duration>,
+        structured_data_over_budget: bool,
+        min_image_width: u32,
+        min_image_height: u32,
+        normalize_structured_data: bool,
     }
     
 
@@ -36,8 +221,22 @@
             serde_json::Value::Array(arr) => arr.get(0).and_then(Self::extract_name_from_value),
             _ => None,
         }
+    }
+    // Helper: JSON-LD "@type" may be a string or an array of strings
+    fn has_json_ld_type(obj: &serde_json::Map<String, serde_json::Value>, wanted: &str) -> bool {
+        match obj.get("@type") {
+            Some(serde_json::Value::String(t)) => t.eq_ignore_ascii_case(wanted),
+            Some(serde_json::Value::Array(types)) => types.iter()
+                .filter_map(|t| t.as_str())
+                .any(|t| t.eq_ignore_ascii_case(wanted)),
+            _ => false,
+        }
     }
         pub fn new(dom: &'a tl::VDom, parser: &'a tl::Parser) -> Self {
+            Self::with_config(dom, parser, &ProcessingConfig::default())
+        }
+
+        pub fn with_config(dom: &'a tl::VDom, parser: &'a tl::Parser, config: &ProcessingConfig) -> Self {
             let mut extractor = Self {
                 dom,
                 parser,
@@ -52,6 +251,16 @@
                 time_nodes: Vec::new(),
                 author_nodes: Vec::new(),
                 canonical_node: None,
+                max_json_ld_blocks: config.max_json_ld_blocks,
+                max_json_ld_bytes: config.max_json_ld_bytes,
+                json_ld_skipped: 0,
+                json_ld_failed: 0,
+                structured_data_budget: config.stage_budgets_ms.get("structured_data")
+                    .map(|ms| std::time::Duration::from_millis(*ms)),
+                structured_data_over_budget: false,
+                min_image_width: config.min_image_width,
+                min_image_height: config.min_image_height,
+                normalize_structured_data: config.normalize_structured_data,
             };
             extractor.collect_metadata();
             extractor
@@ -122,15 +331,58 @@
                 }
             }
 
-            // Parse JSON-LD blocks
-            for node in &self.script_nodes {
-                if let Some(node) = node.get(self.parser) {
-                    let content = node.inner_text(self.parser);
-                    if let Ok(json) = serde_json::from_str(&content) {
-                        self.json_ld_blocks.push(json);
+            // Parse JSON-LD blocks. Scripts are capped by count and total size (category
+            // pages embed dozens of product objects); blocks mentioning the types we read
+            // are parsed first, and parsed blocks keep their document order.
+            let mut candidates: Vec<(usize, bool, String)> = Vec::new();
+            for (position, handle) in self.script_nodes.iter().enumerate() {
+                if let Some(node) = handle.get(self.parser) {
+                    let is_script = node.as_tag()
+                        .map(|tag| tag.name().as_utf8_str().eq_ignore_ascii_case("script"))
+                        .unwrap_or(false);
+                    candidates.push((position, is_script, node.inner_text(self.parser).into_owned()));
+                }
+            }
+            candidates.sort_by_key(|(position, is_script, content)| {
+                let relevant = !*is_script || PRIORITY_JSON_LD_TYPES.iter().any(|t| content.contains(t));
+                (!relevant, *position)
+            });
+
+            let mut parsed: Vec<(usize, serde_json::Value)> = Vec::new();
+            let (mut scripts_parsed, mut bytes_parsed) = (0usize, 0usize);
+            let timer = StageTimer::new(self.structured_data_budget);
+            for (position, is_script, content) in candidates {
+                if timer.exceeded() {
+                    break;
+                }
+                if is_script {
+                    if scripts_parsed >= self.max_json_ld_blocks || bytes_parsed + content.len() > self.max_json_ld_bytes {
+                        self.json_ld_skipped += 1;
+                        continue;
                     }
+                    scripts_parsed += 1;
+                    bytes_parsed += content.len();
                 }
+                match serde_json::from_str(&content) {
+                    Ok(mut json) => {
+                        if self.normalize_structured_data {
+                            FastCleaner::normalize_json_strings(&mut json);
+                        }
+                        parsed.push((position, json));
+                    }
+                    Err(_) if is_script => self.json_ld_failed += 1,
+                    Err(_) => {}
+                }
+            }
+            // Over budget: no structured data rather than whichever blocks happened to finish
+            if timer.exceeded() {
+                self.structured_data_over_budget = true;
+                parsed.clear();
             }
+            parsed.sort_by_key(|(position, _)| *position);
+            // "@graph" members (Yoast, Rank Math) become blocks of their own, so lookups that
+            // only read top-level keys see the Article, Person and Organization nodes
+            self.json_ld_blocks = parsed.into_iter().flat_map(|(_, json)| Self::flatten_json_ld_graph(json)).collect();
 
             // Get title and h1
             self.title = self.dom.query_selector("title")
@@ -144,6 +396,21 @@
                 .map(|node| node.inner_text(self.parser).trim().to_string());
         }
 
+        /// True when JSON-LD parsing overran its stage budget and was dropped
+        pub fn structured_data_over_budget(&self) -> bool {
+            self.structured_data_over_budget
+        }
+
+        /// JSON-LD scripts left unparsed because of the block-count or size cap
+        pub fn json_ld_skipped(&self) -> usize {
+            self.json_ld_skipped
+        }
+
+        /// JSON-LD scripts that were not valid JSON
+        pub fn json_ld_parse_failures(&self) -> usize {
+            self.json_ld_failed
+        }
+
         // Title extraction with priority: OG > Twitter > Title tag > H1
         pub fn get_title(&self) -> Option<String> {
             self.meta_map.get("og:title")
@@ -165,8 +432,156 @@
         }
 
 
+        /// Coordinates (decimal degrees) and place name from schema.org GeoCoordinates (on a
+        /// Place's `geo` or standalone), else the geo.position / ICBM / og:latitude metas.
+        /// Out-of-range coordinates are rejected.
+        pub fn get_geo(&self) -> Option<GeoInfo> {
+            let meta_place = || self.meta_map.get("geo.placename").map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+
+            for obj in self.json_ld_blocks.iter().flat_map(Self::json_ld_nodes) {
+                let (coords, place) = if Self::has_json_ld_type(obj, "GeoCoordinates") {
+                    (obj, None)
+                } else if let Some(geo) = obj.get("geo").and_then(|g| g.as_object()) {
+                    (geo, obj.get("name").and_then(Self::json_ld_text))
+                } else {
+                    continue;
+                };
+                let coordinate = |key: &str| coords.get(key).and_then(|v| match v {
+                    serde_json::Value::Number(n) => n.as_f64(),
+                    other => Self::json_ld_text(other)?.parse().ok(),
+                });
+                if let Some(geo) = Self::geo_info(coordinate("latitude"), coordinate("longitude"), place.or_else(meta_place)) {
+                    return Some(geo);
+                }
+            }
+
+            // "lat;lon" (geo.position) and "lat, lon" (ICBM)
+            let pair = |key: &str| self.meta_map.get(key).and_then(|value| {
+                let mut parts = value.split([';', ',']).map(|p| p.trim().parse::<f64>().ok());
+                Some((parts.next()??, parts.next()??))
+            });
+            let og = |axis: &str| ["og:", "place:location:"].iter()
+                .find_map(|prefix| self.meta_map.get(&format!("{}{}", prefix, axis)))
+                .and_then(|v| v.trim().parse::<f64>().ok());
+            pair("geo.position")
+                .or_else(|| pair("ICBM"))
+                .or_else(|| pair("icbm"))
+                .and_then(|(lat, lon)| Self::geo_info(Some(lat), Some(lon), meta_place()))
+                .or_else(|| Self::geo_info(og("latitude"), og("longitude"), meta_place()))
+        }
+
+        fn geo_info(latitude: Option<f64>, longitude: Option<f64>, place_name: Option<String>) -> Option<GeoInfo> {
+            let (latitude, longitude) = (latitude?, longitude?);
+            ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+                .then_some(GeoInfo { latitude, longitude, place_name })
+        }
+
+        /// ISO-3166 alpha-2 country of the page's JSON-LD postal address (LocalBusiness,
+        /// Organization, ...), used as the dialing region for phone numbers
+        pub fn get_address_country(&self) -> Option<String> {
+            self.json_ld_blocks.iter()
+                .flat_map(Self::json_ld_nodes)
+                .filter_map(|obj| obj.get("address"))
+                .flat_map(|address| match address {
+                    serde_json::Value::Array(items) => items.iter().collect::<Vec<_>>(),
+                    other => vec![other],
+                })
+                .filter_map(|address| address.get("addressCountry"))
+                .filter_map(Self::json_ld_text)
+                .map(|country| country.trim().to_uppercase())
+                .find(|country| country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()))
+        }
+
+        /// Article length declared in JSON-LD wordCount (a number or numeric string)
+        pub fn get_declared_word_count(&self) -> Option<usize> {
+            self.json_ld_blocks.iter()
+                .flat_map(Self::json_ld_nodes)
+                .filter_map(|obj| obj.get("wordCount"))
+                .find_map(|count| match count {
+                    serde_json::Value::Number(n) => n.as_u64().or_else(|| n.as_f64().filter(|f| *f >= 0.0).map(|f| f as u64)),
+                    serde_json::Value::String(s) => s.trim().replace(',', "").parse().ok(),
+                    _ => None,
+                })
+                .map(|count| count as usize)
+                .filter(|&count| count > 0)
+        }
+
+        /// Publisher name for link previews: og:site_name, then application-name
+        pub fn get_site_name(&self) -> Option<String> {
+            self.meta_map.get("og:site_name")
+                .or_else(|| self.meta_map.get("application-name"))
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+        }
+
+        /// Newspaper section from article:section (or JSON-LD articleSection), else the first
+        /// breadcrumb after "Home", else the URL path segments. A candidate only counts when it
+        /// names a `vocabulary` term, directly or through a common alias ("tech", "sport").
+        pub fn get_news_section(&self, url: &str, vocabulary: &[String]) -> Option<String> {
+            let normalize = |raw: &str| -> Option<String> {
+                let key = raw.trim().to_lowercase().replace(['-', '_'], " ");
+                let key = NEWS_SECTION_ALIASES.iter()
+                    .find(|(alias, _)| *alias == key)
+                    .map_or(key.as_str(), |(_, section)| section);
+                vocabulary.iter().find(|term| term.as_str() == key).cloned()
+            };
+
+            let declared = self.meta_map.get("article:section").cloned().into_iter()
+                .chain(self.json_ld_blocks.iter()
+                    .flat_map(Self::json_ld_nodes)
+                    .filter_map(|obj| obj.get("articleSection"))
+                    .filter_map(Self::json_ld_text));
+            for candidate in declared {
+                if let Some(section) = normalize(&candidate) {
+                    return Some(section);
+                }
+            }
+
+            if let Some(section) = self.first_breadcrumb().and_then(|crumb| normalize(&crumb)) {
+                return Some(section);
+            }
+
+            let parsed = url::Url::parse(url).ok()?;
+            parsed.path_segments()?.find_map(normalize)
+        }
+
+        // First breadcrumb entry that is not the home link: JSON-LD BreadcrumbList, else markup
+        fn first_breadcrumb(&self) -> Option<String> {
+            let is_home = |name: &str| name.eq_ignore_ascii_case("home") || self.get_site_name().is_some_and(|site| site.eq_ignore_ascii_case(name));
+
+            let json_ld = self.json_ld_blocks.iter()
+                .flat_map(Self::json_ld_nodes)
+                .filter(|obj| Self::has_json_ld_type(obj, "BreadcrumbList"))
+                .find_map(|obj| {
+                    let mut items: Vec<&serde_json::Map<String, serde_json::Value>> = obj.get("itemListElement")?
+                        .as_array()?
+                        .iter()
+                        .filter_map(|item| item.as_object())
+                        .collect();
+                    items.sort_by_key(|item| item.get("position").and_then(|p| p.as_u64()).unwrap_or(u64::MAX));
+                    items.into_iter()
+                        .filter_map(|item| item.get("name")
+                            .or_else(|| item.get("item").filter(|i| i.is_object()))
+                            .and_then(Self::json_ld_text))
+                        .find(|name| !is_home(name))
+                });
+            if json_ld.is_some() {
+                return json_ld;
+            }
+
+            self.dom.query_selector(BREADCRUMB_SELECTORS).into_iter().flatten()
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                .find_map(|trail| trail.query_selector(self.parser, "a").into_iter().flatten()
+                    .filter_map(|link| link.get(self.parser))
+                    .map(|link| link.inner_text(self.parser).split_whitespace().collect::<Vec<_>>().join(" "))
+                    .find(|name| !name.is_empty() && !is_home(name)))
+        }
+
+
+    /// Up to 15 keywords from article:tag metas, else the keywords meta. Ordered by how
+    /// often a keyword is declared, ties alphabetically, so the output is deterministic.
     pub fn get_keywords(&self) -> Vec<String> {
-    let mut keywords = HashSet::new();
+    let mut keywords: HashMap<String, usize> = HashMap::new();
 
     // 1. Look for modern `article:tag` meta properties
     for node in &self.meta_nodes {
@@ -186,7 +601,7 @@
                         if !tag.is_empty() && tag.len() >= 3
                             
                         {
-                            keywords.insert(tag);
+                            *keywords.entry(tag).or_insert(0) += 1;
                         }
                     }
                 }
@@ -202,76 +617,86 @@
                 if !keyword.is_empty() && keyword.len() >= 3
                   
                 {
-                    keywords.insert(keyword);
+                    *keywords.entry(keyword).or_insert(0) += 1;
                 }
             }
         }
     }
 
     
-    keywords.into_iter().take(15).collect()
+    let mut ranked: Vec<(String, usize)> = keywords.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.into_iter().take(15).map(|(keyword, _)| keyword).collect()
 }
 
 
-        pub fn get_content_type(&self, url: &str) -> String {
-    // 1. JSON-LD @type check
-    for json in &self.json_ld_blocks {
-        if let Some(obj) = json.as_object() {
-            if let Some(type_val) = obj.get("@type").and_then(|v| v.as_str()) {
-                return type_val.to_lowercase();
+        /// Content type candidates, primary first. A declared type is authoritative: JSON-LD
+        /// @type (score 1.0), else a microdata itemtype (0.9), else a specific og:type (0.8).
+        /// Otherwise the primary is the first matching rule in CONTENT_TYPE_RULES order and the
+        /// other matching rules follow ranked by score, hits / (hits + 1) over distinct keywords.
+        /// Pages matching nothing are "article" at 0.5. Each candidate records its `source`.
+        pub fn classify_content_type(&self, url: &str) -> Vec<ContentTypeCandidate> {
+            // 1. Declared types, most to least trustworthy
+            let json_ld_type = self.json_ld_blocks.iter()
+                .filter_map(|json| json.as_object())
+                .find_map(|obj| obj.get("@type").and_then(|v| v.as_str()))
+                .map(|t| (t.to_lowercase(), 1.0, "json_ld"));
+            let microdata_type = || self.dom.query_selector("[itemscope]").into_iter().flatten()
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                .filter_map(Self::microdata_itemtype)
+                .filter_map(|t| t.trim_end_matches('/').rsplit('/').next().map(str::to_string))
+                .find(|t| MICRODATA_CONTENT_TYPES.contains(&t.as_str()))
+                .map(|t| (t, 0.9, "microdata"));
+            let og_type = || self.meta_map.get("og:type")
+                .map(|t| t.trim().to_lowercase())
+                .and_then(|t| t.split('.').next().map(str::to_string))
+                .filter(|family| OG_CONTENT_TYPES.contains(&family.as_str()))
+                .map(|t| (t, 0.8, "og_type"));
+            let declared = json_ld_type.or_else(microdata_type).or_else(og_type);
+
+            // 2. Gather candidate text
+            let mut text = String::new();
+            if let Some(desc) = self.get_description() {
+                text.push_str(&desc.to_lowercase());
             }
-        }
-    }
-
-    // 2. Gather candidate text
-    let mut text = String::new();
-    if let Some(desc) = self.get_description() {
-        text.push_str(&desc.to_lowercase());
-    }
-    if let Some(title) = self.get_title() {
-        text.push_str(" ");
-        text.push_str(&title.to_lowercase());
-    }
-
-    let url_lc = url.to_lowercase();
-    let mut content_type = "article".to_string();
-
-    // 3. FAQ / Q&A
-    if text.contains("faq") || text.contains("question") || text.contains("answer") || text.contains("help") {
-        return "faq".to_string();
-    }
-
-    // 4. Product / e-commerce
-    if text.contains("buy") || text.contains("price") || text.contains("product") ||
-       text.contains("cart") || text.contains("shop") || text.contains("add to basket") {
-        return "product".to_string();
-    }
-
-    // 5. Video
-    if text.contains("video") || text.contains("watch") || text.contains("youtube") || url_lc.contains("video") {
-        return "video".to_string();
-    }
-
-    // 6. Recipe
-    if text.contains("recipe") || text.contains("ingredients") || text.contains("cook") || text.contains("servings") {
-        return "recipe".to_string();
-    }
-
-    // 7. Reviews
-    if text.contains("review") || text.contains("rating") || text.contains("stars") {
-        return "review".to_string();
-    }
-
-    // 8. Blog / News (last fallback before "article")
-    if url_lc.contains("blog") || url_lc.contains("news") || url_lc.contains("post") ||
-       text.contains("blog") || text.contains("news") || text.contains("article") || text.contains("post") ||
-       text.contains("journal") || text.contains("press") || text.contains("update") || text.contains("editorial") {
-        return "blog".to_string();
-    }
+            if let Some(title) = self.get_title() {
+                text.push(' ');
+                text.push_str(&title.to_lowercase());
+            }
+            let url_lc = url.to_lowercase();
+
+            // 3. Keyword rules, in precedence order
+            let mut matched: Vec<ContentTypeCandidate> = CONTENT_TYPE_RULES.iter()
+                .filter_map(|(content_type, text_keywords, url_keywords)| {
+                    let hits = text_keywords.iter().filter(|k| text.contains(*k)).count()
+                        + url_keywords.iter().filter(|k| url_lc.contains(*k)).count();
+                    (hits > 0).then(|| ContentTypeCandidate {
+                        content_type: content_type.to_string(),
+                        score: hits as f32 / (hits as f32 + 1.0),
+                        source: "heuristic".to_string(),
+                    })
+                })
+                .collect();
 
-    content_type
-}
+            let primary = match declared {
+                Some((content_type, score, source)) => {
+                    matched.retain(|candidate| candidate.content_type != content_type);
+                    ContentTypeCandidate { content_type, score, source: source.to_string() }
+                }
+                None if matched.is_empty() => ContentTypeCandidate {
+                    content_type: "article".to_string(),
+                    score: 0.5,
+                    source: "heuristic".to_string(),
+                },
+                None => matched.remove(0),
+            };
+            // Stable sort keeps precedence order among equal scores
+            matched.sort_by(|a, b| b.score.total_cmp(&a.score));
 
+            let mut candidates = vec![primary];
+            candidates.extend(matched);
+            candidates
+        }
 
         pub fn get_primary_image(&self, resolve_url: impl Fn(&str) -> String) -> Option<ImageInfo> {
             // 1. Check JSON-LD
@@ -281,7 +706,11 @@
                         let url = match image_val {
                             serde_json::Value::String(s) => Some(s.clone()),
                             serde_json::Value::Object(img_obj) => {
-                                img_obj.get("url").and_then(|u| u.as_str()).map(|s| s.to_string())
+                                let width = img_obj.get("width").and_then(Self::json_ld_dimension);
+                                let height = img_obj.get("height").and_then(Self::json_ld_dimension);
+                                img_obj.get("url").and_then(|u| u.as_str())
+                                    .filter(|_| self.meets_min_image_size(width, height))
+                                    .map(|s| s.to_string())
                             }
                             _ => None,
                         };
@@ -297,10 +726,14 @@
 
             // 2. Check OG image
             if let Some(og_image) = self.meta_map.get("og:image") {
-                return Some(ImageInfo { 
-                    src: resolve_url(og_image), 
-                    alt: "Featured image".to_string() 
-                });
+                let width = self.meta_map.get("og:image:width").and_then(|w| Self::parse_dimension(w));
+                let height = self.meta_map.get("og:image:height").and_then(|h| Self::parse_dimension(h));
+                if self.meets_min_image_size(width, height) {
+                    return Some(ImageInfo { 
+                        src: resolve_url(og_image), 
+                        alt: "Featured image".to_string() 
+                    });
+                }
             }
 
             // 3. Check first meaningful image
@@ -309,8 +742,12 @@
                     let attrs = tag.attributes();
                     if let Some(src) = attrs.get("src").and_then(|s| s.map(|s| s.as_utf8_str())) {
                         let src_str = src.to_string();
+                        let width = attrs.get("width").flatten().and_then(|w| Self::parse_dimension(&w.as_utf8_str()));
+                        let height = attrs.get("height").flatten().and_then(|h| Self::parse_dimension(&h.as_utf8_str()));
                         // Skip small icons, logos, favicons
-                        if !src_str.contains("icon") && !src_str.contains("logo") && !src_str.contains("favicon") {
+                        if !src_str.contains("icon") && !src_str.contains("logo") && !src_str.contains("favicon")
+                            && self.meets_min_image_size(width, height)
+                        {
                             let alt = attrs.get("alt")
                                 .and_then(|a| a.map(|a| a.as_utf8_str().to_string()))
                                 .unwrap_or_default();
@@ -326,6 +763,252 @@
             None
         }
 
+        /// Every <img> with a srcset (or lazy-loading data-srcset): its src, sizes and all
+        /// candidates with resolved URLs, in declaration order
+        pub fn get_responsive_images(&self, resolve_url: impl Fn(&str) -> String) -> Vec<ResponsiveImage> {
+            self.img_nodes.iter()
+                .filter_map(|node| node.get(self.parser).and_then(|n| n.as_tag()))
+                .filter_map(|tag| {
+                    let attrs = tag.attributes();
+                    let attr = |name: &str| attrs.get(name).flatten()
+                        .map(|v| v.as_utf8_str().trim().to_string())
+                        .filter(|v| !v.is_empty());
+                    let candidates: Vec<SrcsetCandidate> = Self::parse_srcset(&attr("srcset").or_else(|| attr("data-srcset"))?)
+                        .into_iter()
+                        .map(|candidate| SrcsetCandidate { url: resolve_url(&candidate.url), ..candidate })
+                        .collect();
+                    (!candidates.is_empty()).then(|| ResponsiveImage {
+                        src: attr("src").map(|src| resolve_url(&src)),
+                        alt: attr("alt").unwrap_or_default(),
+                        sizes: attr("sizes"),
+                        candidates,
+                    })
+                })
+                .collect()
+        }
+
+        // HTML srcset: "url [descriptor], ...". URLs may themselves contain commas
+        // (image CDNs: "w_400,h_300"), so a candidate's URL runs to the next whitespace
+        // and only a trailing comma ends it. Candidates with malformed descriptors are dropped.
+        fn parse_srcset(srcset: &str) -> Vec<SrcsetCandidate> {
+            let mut candidates = Vec::new();
+            let mut rest = srcset;
+            loop {
+                rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+                if rest.is_empty() {
+                    break;
+                }
+                let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let raw_url = &rest[..url_end];
+                rest = &rest[url_end..];
+                let url = raw_url.trim_end_matches(',');
+                let descriptor = if url.len() < raw_url.len() {
+                    ""
+                } else {
+                    let descriptor_end = rest.find(',').unwrap_or(rest.len());
+                    let descriptor = rest[..descriptor_end].trim();
+                    rest = &rest[descriptor_end..];
+                    descriptor
+                };
+
+                let (width, density) = match descriptor {
+                    "" => (None, Some(1.0)),
+                    d if d.ends_with('w') => match d[..d.len() - 1].parse::<u32>() {
+                        Ok(w) if w > 0 => (Some(w), None),
+                        _ => continue,
+                    },
+                    d if d.ends_with('x') => match d[..d.len() - 1].parse::<f32>() {
+                        Ok(x) if x > 0.0 => (None, Some(x)),
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+                candidates.push(SrcsetCandidate { url: url.to_string(), width, density });
+            }
+            candidates
+        }
+
+        // Undeclared dimensions pass; only a declared size below the minimum rejects
+        fn meets_min_image_size(&self, width: Option<u32>, height: Option<u32>) -> bool {
+            width.is_none_or(|w| w >= self.min_image_width) && height.is_none_or(|h| h >= self.min_image_height)
+        }
+
+        // Pixel size from "640", "640px" or "640.0"; percentages and other units are unknown
+        fn parse_dimension(value: &str) -> Option<u32> {
+            let value = value.trim();
+            let number = value.strip_suffix("px").unwrap_or(value).trim();
+            number.parse::<f64>().ok().filter(|n| *n >= 0.0).map(|n| n as u32)
+        }
+
+        // JSON-LD width/height: a number, a string, or a QuantitativeValue { "value": ... }
+        fn json_ld_dimension(value: &serde_json::Value) -> Option<u32> {
+            match value {
+                serde_json::Value::Number(n) => n.as_f64().filter(|n| *n >= 0.0).map(|n| n as u32),
+                serde_json::Value::String(s) => Self::parse_dimension(s),
+                serde_json::Value::Object(obj) => obj.get("value").and_then(Self::json_ld_dimension),
+                _ => None,
+            }
+        }
+
+        /// Meta tags whose property/name starts with `prefix` ("twitter:"), keyed by the rest
+        /// of the lowercased name; a repeated key keeps its first value in document order
+        pub fn get_prefixed_meta(&self, prefix: &str) -> HashMap<String, String> {
+            let mut entries = HashMap::new();
+            for node in &self.meta_nodes {
+                let Some(tag) = node.get(self.parser).and_then(|n| n.as_tag()) else { continue };
+                let attrs = tag.attributes();
+                let Some(key) = attrs.get("property").flatten().or_else(|| attrs.get("name").flatten())
+                    .map(|k| k.as_utf8_str().trim().to_lowercase()) else { continue };
+                let Some(content) = attrs.get("content").flatten().map(|c| c.as_utf8_str().trim().to_string()) else { continue };
+                let Some(stripped) = key.strip_prefix(prefix).filter(|rest| !rest.is_empty()) else { continue };
+                if !content.is_empty() {
+                    entries.entry(stripped.to_string()).or_insert(content);
+                }
+            }
+            entries
+        }
+
+        /// All og:* properties in document order, grouping repeated keys
+        /// (multiple og:image tags) into one entry with several values
+        pub fn get_open_graph(&self) -> Vec<(String, Vec<String>)> {
+            let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+
+            for node in &self.meta_nodes {
+                let Some(tag) = node.get(self.parser).and_then(|n| n.as_tag()) else { continue };
+                let attrs = tag.attributes();
+                let Some(property) = attrs.get("property").flatten().map(|p| p.as_utf8_str().trim().to_lowercase()) else { continue };
+                let Some(content) = attrs.get("content").flatten().map(|c| c.as_utf8_str().trim().to_string()) else { continue };
+                if !property.starts_with("og:") || content.is_empty() {
+                    continue;
+                }
+
+                match entries.iter_mut().find(|(key, _)| *key == property) {
+                    Some((_, values)) => values.push(content),
+                    None => entries.push((property, vec![content])),
+                }
+            }
+
+            entries
+        }
+
+        /// Collect og:video:* and og:audio:* groups in document order.
+        /// A bare og:video / og:video:url tag opens a new object; the structured
+        /// properties that follow (secure_url, type, width, height) attach to it.
+        pub fn get_og_media(&self, resolve_url: impl Fn(&str) -> String) -> Vec<MediaObject> {
+            let mut media: Vec<MediaObject> = Vec::new();
+
+            for node in &self.meta_nodes {
+                let Some(tag) = node.get(self.parser).and_then(|n| n.as_tag()) else { continue };
+                let attrs = tag.attributes();
+                let Some(property) = attrs.get("property").flatten().map(|p| p.as_utf8_str().to_lowercase()) else { continue };
+                let Some(content) = attrs.get("content").flatten().map(|c| c.as_utf8_str().trim().to_string()) else { continue };
+                if content.is_empty() {
+                    continue;
+                }
+
+                let Some(rest) = property.strip_prefix("og:") else { continue };
+                let (kind, field) = match rest.split_once(':') {
+                    Some((kind, field)) => (kind, field),
+                    None => (rest, "url"),
+                };
+                if kind != "video" && kind != "audio" {
+                    continue;
+                }
+
+                // A new url opens a new group; structured properties before any url
+                // still need a group to land in
+                let idx = match media.iter().rposition(|m| m.kind == kind) {
+                    Some(idx) if field != "url" || media[idx].url.is_empty() => idx,
+                    _ => {
+                        media.push(MediaObject {
+                            kind: kind.to_string(),
+                            url: String::new(),
+                            secure_url: None,
+                            mime_type: None,
+                            width: None,
+                            height: None,
+                        });
+                        media.len() - 1
+                    }
+                };
+                let entry = &mut media[idx];
+
+                match field {
+                    "url" => entry.url = resolve_url(&content),
+                    "secure_url" => entry.secure_url = Some(resolve_url(&content)),
+                    "type" => entry.mime_type = Some(content),
+                    "width" => entry.width = content.parse().ok(),
+                    "height" => entry.height = content.parse().ok(),
+                    _ => {}
+                }
+            }
+
+            // A group with only a secure_url is still usable
+            for entry in &mut media {
+                if entry.url.is_empty() {
+                    if let Some(secure) = &entry.secure_url {
+                        entry.url = secure.clone();
+                    }
+                }
+            }
+            media.retain(|m| !m.url.is_empty());
+            media
+        }
+
+        // Non-empty content of every meta tag whose property or name is `key`, in document order
+        fn meta_contents(&self, key: &str) -> Vec<String> {
+            self.meta_nodes.iter()
+                .filter_map(|node| node.get(self.parser).and_then(|n| n.as_tag()))
+                .filter(|tag| {
+                    let attrs = tag.attributes();
+                    ["property", "name"].iter().any(|attr| attrs.get(*attr).flatten()
+                        .is_some_and(|value| value.as_utf8_str().trim().eq_ignore_ascii_case(key)))
+                })
+                .filter_map(|tag| tag.attributes().get("content").flatten().map(|c| c.as_utf8_str().trim().to_string()))
+                .filter(|content| !content.is_empty())
+                .collect()
+        }
+
+        /// Accent color as "#RRGGBB": the first valid <meta name="theme-color"> (pages may
+        /// declare several per color scheme), else msapplication-TileColor
+        pub fn get_theme_color(&self) -> Option<String> {
+            let declared = |wanted: &str| -> Vec<String> {
+                self.meta_nodes.iter()
+                    .filter_map(|node| node.get(self.parser).and_then(|n| n.as_tag()))
+                    .filter(|tag| tag.attributes().get("name").flatten()
+                        .map(|name| name.as_utf8_str().trim().eq_ignore_ascii_case(wanted))
+                        .unwrap_or(false))
+                    .filter_map(|tag| tag.attributes().get("content").flatten().map(|c| c.as_utf8_str().to_string()))
+                    .collect()
+            };
+            declared("theme-color").into_iter()
+                .chain(declared("msapplication-TileColor"))
+                .find_map(|color| Self::normalize_color(&color))
+        }
+
+        // "#RGB", "#RRGGBB", "#RRGGBBAA" (alpha dropped) or "rgb(r, g, b)" as "#RRGGBB"
+        fn normalize_color(value: &str) -> Option<String> {
+            let value = value.trim().to_lowercase();
+            if let Some(hex) = value.strip_prefix('#') {
+                if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return None;
+                }
+                let rgb = match hex.len() {
+                    3 | 4 => hex.chars().take(3).flat_map(|c| [c, c]).collect::<String>(),
+                    6 | 8 => hex[..6].to_string(),
+                    _ => return None,
+                };
+                return Some(format!("#{}", rgb.to_uppercase()));
+            }
+            let args = value.strip_prefix("rgba(").or_else(|| value.strip_prefix("rgb("))?.strip_suffix(')')?;
+            let channels: Vec<u8> = args.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|part| !part.is_empty() && *part != "/")
+                .take(3)
+                .map(|part| part.parse::<u8>().ok())
+                .collect::<Option<_>>()?;
+            (channels.len() == 3).then(|| format!("#{:02X}{:02X}{:02X}", channels[0], channels[1], channels[2]))
+        }
+
         pub fn get_favicon(&self, resolve_url: impl Fn(&str) -> String) -> Option<String> {
             // Check for favicon links by examining rel attributes directly
             for node in &self.link_nodes {
@@ -406,170 +1089,1295 @@
         }
 
 
-        pub fn get_author(&self) -> Option<String> {
-            // 1. Check specific meta tags first
-            if let Some(author) = self.meta_map.get("article:author").or_else(|| self.meta_map.get("author")) {
-                let author = author.trim();
-                if !author.is_empty() { return Some(author.to_string()); }
+        /// Name of the JSON-LD publisher; stands in for the author when none is credited
+        pub fn get_publisher_name(&self) -> Option<String> {
+            self.json_ld_blocks.iter()
+                .filter_map(|json| json.as_object()?.get("publisher"))
+                .filter_map(Self::extract_name_from_value)
+                .map(|name| name.trim().to_string())
+                .find(|name| !name.is_empty())
+        }
+
+
+        /// All credited authors in citation order, deduplicated case-insensitively (first
+        /// occurrence wins). JSON-LD author lists are authoritative; otherwise every
+        /// article:author meta tag (profile URLs skipped) or the author meta tag, then byline
+        /// elements, with "By A, B and C" split into separate names.
+        pub fn get_authors(&self) -> Vec<String> {
+            let mut names: Vec<String> = self.json_ld_blocks.iter()
+                .flat_map(Self::json_ld_nodes)
+                .filter_map(|obj| obj.get("author"))
+                .flat_map(Self::extract_names_from_value)
+                .collect();
+
+            if names.is_empty() {
+                let mut declared = self.meta_contents("article:author");
+                declared.retain(|author| !author.starts_with("http://") && !author.starts_with("https://"));
+                if declared.is_empty() {
+                    declared = self.meta_contents("author");
+                }
+                names = declared.iter().flat_map(|author| Self::split_byline(author)).collect();
+            }
+
+            if names.is_empty() {
+                let byline_nodes = self.dom.query_selector(".byline")
+                    .map(|iter| iter.collect::<Vec<_>>())
+                    .unwrap_or_default();
+                for node in self.author_nodes.iter().chain(byline_nodes.iter()) {
+                    if let Some(element) = node.get(self.parser) {
+                        let text = element.inner_text(self.parser);
+                        if text.trim().len() < 200 {
+                            names.extend(Self::split_byline(&text));
+                        }
+                    }
+                }
+            }
+
+            let mut seen = std::collections::HashSet::new();
+            names.retain(|name| seen.insert(name.to_lowercase()));
+            names
+        }
+
+        /// (bio, profile links) of `author`, normally the first of `get_authors`. A JSON-LD
+        /// Person with that name supplies description and sameAs; otherwise the first author
+        /// box or byline naming the author supplies its first paragraph of
+        /// MIN_AUTHOR_BIO_WORDS+ words and links to known social hosts (or marked rel="me").
+        /// Links are resolved and deduplicated.
+        pub fn get_author_profile(&self, author: &str, resolve_url: impl Fn(&str) -> String) -> (Option<String>, Vec<String>) {
+            let (bio, mut links) = self.json_ld_author_profile(author, &resolve_url)
+                .or_else(|| self.author_box_profile(author, &resolve_url))
+                .unwrap_or_default();
+
+            let mut seen = HashSet::new();
+            links.retain(|link| !link.is_empty() && seen.insert(link.clone()));
+            (bio, links)
+        }
+
+        // description and sameAs of the JSON-LD Person named `author`
+        fn json_ld_author_profile(&self, author: &str, resolve_url: &impl Fn(&str) -> String) -> Option<(Option<String>, Vec<String>)> {
+            // The Person is either a node of its own (@graph) or inlined as an author value
+            let nodes: Vec<_> = self.json_ld_blocks.iter().flat_map(Self::json_ld_nodes).collect();
+            let inline_authors = nodes.iter()
+                .filter_map(|obj| obj.get("author"))
+                .flat_map(|value| value.as_array().map_or_else(|| vec![value], |items| items.iter().collect()))
+                .filter_map(|value| value.as_object());
+            let person = nodes.iter().copied()
+                .chain(inline_authors)
+                .filter(|obj| obj.get("name").and_then(Self::json_ld_text).is_some_and(|name| name.eq_ignore_ascii_case(author)))
+                .find(|obj| obj.contains_key("description") || obj.contains_key("sameAs"))?;
+            let links = match person.get("sameAs") {
+                Some(serde_json::Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).map(|u| resolve_url(u.trim())).collect(),
+                Some(serde_json::Value::String(url)) => vec![resolve_url(url.trim())],
+                _ => Vec::new(),
+            };
+            Some((person.get("description").and_then(Self::json_ld_text), links))
+        }
+
+        // Bio paragraph and profile links of the first author box or byline naming `author`;
+        // boxes of other people (e.g. commenters' .author nodes) are skipped
+        fn author_box_profile(&self, author: &str, resolve_url: &impl Fn(&str) -> String) -> Option<(Option<String>, Vec<String>)> {
+            let author = author.to_lowercase();
+            let container = self.dom.query_selector(AUTHOR_BOX_SELECTORS)?
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                .find(|tag| tag.inner_text(self.parser).to_lowercase().contains(&author))?;
+
+            let bio = container.query_selector(self.parser, "p").into_iter().flatten()
+                .filter_map(|handle| handle.get(self.parser))
+                .map(|p| p.inner_text(self.parser).split_whitespace().collect::<Vec<_>>().join(" "))
+                .find(|text| text.split_whitespace().count() >= MIN_AUTHOR_BIO_WORDS);
+            let mut links = Vec::new();
+            for link in container.query_selector(self.parser, "a").into_iter().flatten()
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+            {
+                let attrs = link.attributes();
+                let Some(href) = attrs.get("href").flatten().map(|h| h.as_utf8_str().trim().to_string()) else { continue };
+                let is_rel_me = attrs.get("rel").flatten()
+                    .is_some_and(|rel| rel.as_utf8_str().split_whitespace().any(|r| r.eq_ignore_ascii_case("me")));
+                // Relative links stay on the page's own site, which may itself be a social host
+                let is_social = url::Url::parse(&href).ok()
+                    .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_lowercase()))
+                    .is_some_and(|host| AUTHOR_SOCIAL_HOSTS.iter().any(|social| host == *social || host.ends_with(&format!(".{}", social))));
+                if is_rel_me || is_social {
+                    links.push(resolve_url(&href));
+                }
+            }
+            Some((bio, links))
+        }
+
+        // Every name in a JSON-LD author value, keeping array order
+        fn extract_names_from_value(val: &serde_json::Value) -> Vec<String> {
+            match val {
+                serde_json::Value::Array(items) => items.iter().flat_map(Self::extract_names_from_value).collect(),
+                _ => Self::extract_name_from_value(val)
+                    .map(|name| name.split_whitespace().collect::<Vec<_>>().join(" "))
+                    .filter(|name| !name.is_empty())
+                    .into_iter()
+                    .collect(),
+            }
+        }
+
+        /// Split a byline such as "By Jane Doe, John Roe and Ann Poe" into ordered names.
+        /// Commas only separate names when every part is multi-word, so "Doe, Jane" stays whole.
+        fn split_byline(byline: &str) -> Vec<String> {
+            let text = byline.split_whitespace().collect::<Vec<_>>().join(" ");
+            let lower = text.to_lowercase();
+            let text = ["written by ", "posted by ", "by "].iter()
+                .find(|prefix| lower.starts_with(*prefix))
+                .map(|prefix| &text[prefix.len()..])
+                .unwrap_or(&text);
+
+            let mut parts: Vec<&str> = Vec::new();
+            for segment in text.split(" and ").flat_map(|s| s.split(" & ")) {
+                let commas: Vec<&str> = segment.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+                if commas.len() > 1 && commas.iter().all(|p| p.contains(' ')) {
+                    parts.extend(commas);
+                } else {
+                    parts.push(segment.trim().trim_matches(','));
+                }
             }
 
-            // 2. Check JSON-LD with a robust parser
+            parts.into_iter()
+                .map(str::trim)
+                .filter(|name| !name.is_empty() && name.len() < 100)
+                .map(str::to_string)
+                .collect()
+        }
+
+        /// Raw ISO-8601 durations from JSON-LD Recipe and VideoObject nodes, as (field, value)
+        pub fn get_durations(&self) -> Vec<(String, String)> {
+            let mut durations = Vec::new();
+
             for json in &self.json_ld_blocks {
-                if let Some(obj) = json.as_object() {
-                    // Try author field, then publisher
-                    if let Some(author_val) = obj.get("author") {
-                        if let Some(name) = Self::extract_name_from_value(author_val) {
-                            if !name.trim().is_empty() { return Some(name.trim().to_string()); }
+                let Some(obj) = json.as_object() else { continue };
+
+                // Videos are often nested under an Article's "video" property
+                let mut candidates = vec![obj];
+                if let Some(video) = obj.get("video").and_then(|v| v.as_object()) {
+                    candidates.push(video);
+                }
+
+                for candidate in candidates {
+                    let fields: &[&str] = if Self::has_json_ld_type(candidate, "Recipe") {
+                        &["prepTime", "cookTime", "totalTime"]
+                    } else if Self::has_json_ld_type(candidate, "VideoObject") {
+                        &["duration"]
+                    } else {
+                        continue;
+                    };
+
+                    for field in fields {
+                        if let Some(value) = candidate.get(*field).and_then(|v| v.as_str()) {
+                            durations.push((field.to_string(), value.trim().to_string()));
                         }
                     }
-                    if let Some(publisher_val) = obj.get("publisher") {
-                        if let Some(name) = Self::extract_name_from_value(publisher_val) {
-                            if !name.trim().is_empty() { return Some(name.trim().to_string()); }
+                }
+            }
+
+            durations
+        }
+
+
+        /// Recipe merged from JSON-LD (preferred), microdata and h-recipe markup; later
+        /// sources only fill fields the earlier ones left empty.
+        /// `parse_duration` converts ISO-8601 durations to seconds.
+        pub fn get_recipe(&self, parse_duration: impl Fn(&str) -> Option<u64>) -> Option<RecipeData> {
+            let sources = [
+                self.get_json_ld_recipe(&parse_duration),
+                self.get_microdata_recipe(&parse_duration),
+                self.get_h_recipe(&parse_duration),
+            ];
+
+            let mut merged: Option<RecipeData> = None;
+            for source in sources.into_iter().flatten() {
+                match merged.as_mut() {
+                    Some(recipe) => Self::fill_recipe_gaps(recipe, source),
+                    None => merged = Some(source),
+                }
+            }
+
+            if let Some(recipe) = merged.as_mut() {
+                recipe.servings = recipe.recipe_yield.as_deref().and_then(Self::yield_servings);
+            }
+            merged.filter(|r| r.name.is_some() || !r.ingredients.is_empty() || !r.instructions.is_empty())
+        }
+
+        // First whole number in a recipe yield: "4 servings" -> 4, "Serves 6-8" -> 6
+        fn yield_servings(recipe_yield: &str) -> Option<u32> {
+            let digits: String = recipe_yield.chars()
+                .skip_while(|c| !c.is_ascii_digit())
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            digits.parse().ok().filter(|servings| *servings > 0)
+        }
+
+        fn fill_recipe_gaps(recipe: &mut RecipeData, other: RecipeData) {
+            recipe.name = recipe.name.take().or(other.name);
+            if recipe.ingredients.is_empty() {
+                recipe.ingredients = other.ingredients;
+            }
+            if recipe.instructions.is_empty() {
+                recipe.instructions = other.instructions;
+            }
+            recipe.prep_time = recipe.prep_time.or(other.prep_time);
+            recipe.cook_time = recipe.cook_time.or(other.cook_time);
+            recipe.total_time = recipe.total_time.or(other.total_time);
+            recipe.recipe_yield = recipe.recipe_yield.take().or(other.recipe_yield);
+            recipe.rating = recipe.rating.or(other.rating);
+            for (key, value) in other.nutrition {
+                recipe.nutrition.entry(key).or_insert(value);
+            }
+        }
+
+        fn get_json_ld_recipe(&self, parse_duration: &impl Fn(&str) -> Option<u64>) -> Option<RecipeData> {
+            let recipe = self.json_ld_blocks.iter()
+                .flat_map(Self::json_ld_nodes)
+                .find(|obj| Self::has_json_ld_type(obj, "Recipe"))?;
+            let text = |key: &str| recipe.get(key).and_then(Self::json_ld_text);
+
+            Some(RecipeData {
+                name: text("name"),
+                ingredients: recipe.get("recipeIngredient")
+                    .or_else(|| recipe.get("ingredients"))
+                    .map(|v| match v {
+                        serde_json::Value::Array(items) => items.iter().filter_map(Self::json_ld_text).collect(),
+                        other => Self::json_ld_text(other).into_iter().collect(),
+                    })
+                    .unwrap_or_default(),
+                instructions: recipe.get("recipeInstructions")
+                    .map(Self::json_ld_instructions)
+                    .unwrap_or_default(),
+                prep_time: text("prepTime").and_then(|d| parse_duration(&d)),
+                cook_time: text("cookTime").and_then(|d| parse_duration(&d)),
+                total_time: text("totalTime").and_then(|d| parse_duration(&d)),
+                recipe_yield: text("recipeYield"),
+                servings: None,
+                nutrition: recipe.get("nutrition")
+                    .and_then(|n| n.as_object())
+                    .map(|n| n.iter()
+                        .filter(|(key, _)| !key.starts_with('@'))
+                        .filter_map(|(key, value)| Some((key.clone(), Self::json_ld_text(value)?)))
+                        .collect())
+                    .unwrap_or_default(),
+                rating: Self::json_ld_rating(recipe).0,
+            })
+        }
+
+        // (ratingValue, reviewCount or else ratingCount) of a node's aggregateRating
+        fn json_ld_rating(obj: &serde_json::Map<String, serde_json::Value>) -> (Option<f64>, Option<u64>) {
+            let Some(rating) = obj.get("aggregateRating").and_then(|r| r.as_object()) else { return (None, None) };
+            let text = |key: &str| rating.get(key).and_then(Self::json_ld_text);
+            let value = text("ratingValue").and_then(|v| Self::parse_rating(&v));
+            let count = text("reviewCount").and_then(|v| Self::parse_count(&v))
+                .or_else(|| text("ratingCount").and_then(|v| Self::parse_count(&v)));
+            (value, count)
+        }
+
+        // Rating value, where a comma is a decimal separator ("4,5")
+        fn parse_rating(raw: &str) -> Option<f64> {
+            raw.trim().replace(',', ".").parse().ok()
+        }
+
+        // Review or rating count, where commas, spaces and apostrophes group thousands ("1,234")
+        fn parse_count(raw: &str) -> Option<u64> {
+            let digits: String = raw.trim().chars().filter(|c| !matches!(c, ',' | ' ' | '\u{a0}' | '\'')).collect();
+            digits.parse().ok().or_else(|| digits.parse::<f64>().ok().filter(|c| *c >= 0.0).map(|c| c as u64))
+        }
+
+        // A block with an "@graph" as its members, in order; the wrapper is kept only when it
+        // carries properties of its own besides "@context". Author and publisher references
+        // by bare "@id" get the name of the graph node they point at.
+        fn flatten_json_ld_graph(json: serde_json::Value) -> Vec<serde_json::Value> {
+            let serde_json::Value::Object(mut obj) = json else { return vec![json] };
+            let mut members = match obj.remove("@graph") {
+                Some(serde_json::Value::Array(members)) => members,
+                Some(member) => vec![member],
+                None => return vec![serde_json::Value::Object(obj)],
+            };
+
+            let names: HashMap<String, serde_json::Value> = members.iter()
+                .filter_map(|member| {
+                    let id = member.get("@id")?.as_str()?;
+                    Some((id.to_string(), member.get("name")?.clone()))
+                })
+                .collect();
+            for member in members.iter_mut().filter_map(|m| m.as_object_mut()) {
+                for key in ["author", "publisher"] {
+                    let references: Vec<&mut serde_json::Value> = match member.get_mut(key) {
+                        Some(serde_json::Value::Array(items)) => items.iter_mut().collect(),
+                        Some(value) => vec![value],
+                        None => Vec::new(),
+                    };
+                    for reference in references.into_iter().filter_map(|r| r.as_object_mut()) {
+                        if reference.contains_key("name") {
+                            continue;
+                        }
+                        if let Some(name) = reference.get("@id").and_then(|id| id.as_str()).and_then(|id| names.get(id)) {
+                            reference.insert("name".to_string(), name.clone());
                         }
                     }
                 }
             }
 
-            // 3. Fallback to CSS selectors
-            for node in &self.author_nodes {
-                if let Some(element) = node.get(self.parser) {
-                    let author_text = element.inner_text(self.parser).trim().to_string();
-                    if !author_text.is_empty() && author_text.len() < 100 {
-                        return Some(author_text);
+            let mut blocks = Vec::with_capacity(members.len() + 1);
+            if obj.keys().any(|key| key != "@context") {
+                blocks.push(serde_json::Value::Object(obj));
+            }
+            blocks.extend(members);
+            blocks
+        }
+
+        // Top-level objects of a JSON-LD block, including arrays and "@graph" members
+        fn json_ld_nodes(value: &serde_json::Value) -> Vec<&serde_json::Map<String, serde_json::Value>> {
+            match value {
+                serde_json::Value::Object(obj) => {
+                    let mut nodes = vec![obj];
+                    if let Some(graph) = obj.get("@graph") {
+                        nodes.extend(Self::json_ld_nodes(graph));
                     }
+                    nodes
                 }
+                serde_json::Value::Array(items) => items.iter().flat_map(Self::json_ld_nodes).collect(),
+                _ => Vec::new(),
             }
+        }
 
-            None
+        // Scalar text of a JSON-LD value; arrays yield their first entry
+        fn json_ld_text(value: &serde_json::Value) -> Option<String> {
+            match value {
+                serde_json::Value::String(s) => {
+                    let text = s.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if text.is_empty() { None } else { Some(text) }
+                }
+                serde_json::Value::Number(n) => Some(n.to_string()),
+                serde_json::Value::Array(items) => items.iter().find_map(Self::json_ld_text),
+                serde_json::Value::Object(obj) => obj.get("text")
+                    .or_else(|| obj.get("name"))
+                    .and_then(Self::json_ld_text),
+                _ => None,
+            }
+        }
+
+        // recipeInstructions may be one string, a list of strings, HowToStep objects
+        // or HowToSection objects wrapping steps
+        fn json_ld_instructions(value: &serde_json::Value) -> Vec<String> {
+            match value {
+                serde_json::Value::String(s) => s.lines()
+                    .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                    .filter(|line| !line.is_empty())
+                    .collect(),
+                serde_json::Value::Array(items) => items.iter().flat_map(Self::json_ld_instructions).collect(),
+                serde_json::Value::Object(obj) => match obj.get("itemListElement") {
+                    Some(steps) => Self::json_ld_instructions(steps),
+                    None => Self::json_ld_text(value).into_iter().collect(),
+                },
+                _ => Vec::new(),
+            }
         }
 
+        /// schema.org HowTo from JSON-LD: name, steps in document order (sections flattened)
+        /// and totalTime in seconds. Step images are resolved with `resolve_url`.
+        pub fn get_howto(
+            &self,
+            parse_duration: impl Fn(&str) -> Option<u64>,
+            resolve_url: impl Fn(&str) -> String,
+        ) -> Option<HowToData> {
+            let howto = self.json_ld_blocks.iter()
+                .flat_map(Self::json_ld_nodes)
+                .find(|obj| Self::has_json_ld_type(obj, "HowTo"))?;
+
+            let mut steps = Vec::new();
+            if let Some(step_value) = howto.get("step") {
+                Self::collect_howto_steps(step_value, &resolve_url, &mut steps);
+            }
 
-        pub fn get_canonical_url(&self, base_url: &str) -> Option<String> {
-            if let Some(node) = self.canonical_node {
-                if let Some(tag) = node.get(self.parser).and_then(|n| n.as_tag()) {
-                    if let Some(href) = tag.attributes().get("href").and_then(|h| h.map(|h| h.as_utf8_str())) {
-                        let canonical_url = href.to_string();
-                        if canonical_url != base_url && !canonical_url.is_empty() {
-                            return Some(canonical_url);
+            Some(HowToData {
+                name: howto.get("name").and_then(Self::json_ld_text),
+                steps,
+                total_time: howto.get("totalTime").and_then(Self::json_ld_text).and_then(|d| parse_duration(&d)),
+            })
+        }
+
+        // HowToStep objects, bare strings, and HowToSection / ItemList wrappers of either
+        fn collect_howto_steps(value: &serde_json::Value, resolve_url: &impl Fn(&str) -> String, steps: &mut Vec<HowToStep>) {
+            match value {
+                serde_json::Value::Array(items) => {
+                    for item in items {
+                        Self::collect_howto_steps(item, resolve_url, steps);
+                    }
+                }
+                serde_json::Value::Object(obj) if obj.contains_key("itemListElement") => {
+                    Self::collect_howto_steps(&obj["itemListElement"], resolve_url, steps);
+                }
+                serde_json::Value::Object(obj) => {
+                    let name = obj.get("name").and_then(Self::json_ld_text);
+                    let Some(text) = obj.get("text").and_then(Self::json_ld_text).or_else(|| name.clone()) else {
+                        return;
+                    };
+                    let image = obj.get("image").and_then(Self::json_ld_image_url).map(|src| resolve_url(&src));
+                    steps.push(HowToStep { name, text, image });
+                }
+                other => {
+                    if let Some(text) = Self::json_ld_text(other) {
+                        steps.push(HowToStep { name: None, text, image: None });
+                    }
+                }
+            }
+        }
+
+        /// schema.org Product merged from JSON-LD (preferred) and microdata; price, currency
+        /// and availability come from the first priced Offer. None without product markup.
+        pub fn get_product(&self) -> Option<ProductData> {
+            let mut merged: Option<ProductData> = None;
+            for source in [self.get_json_ld_product(), self.get_microdata_product()].into_iter().flatten() {
+                match merged.as_mut() {
+                    Some(product) => Self::fill_product_gaps(product, source),
+                    None => merged = Some(source),
+                }
+            }
+
+            merged.filter(|p| p.name.is_some() || p.price.is_some() || p.sku.is_some())
+        }
+
+        fn fill_product_gaps(product: &mut ProductData, other: ProductData) {
+            product.name = product.name.take().or(other.name);
+            product.price = product.price.or(other.price);
+            product.currency = product.currency.take().or(other.currency);
+            product.availability = product.availability.take().or(other.availability);
+            product.sku = product.sku.take().or(other.sku);
+            product.brand = product.brand.take().or(other.brand);
+            product.rating = product.rating.or(other.rating);
+            product.review_count = product.review_count.or(other.review_count);
+        }
+
+        fn get_json_ld_product(&self) -> Option<ProductData> {
+            let nodes: Vec<_> = self.json_ld_blocks.iter().flat_map(Self::json_ld_nodes).collect();
+            // A Product node with its offers, or a bare Offer naming its product in itemOffered
+            let (product, offers) = match nodes.iter().find(|obj| Self::has_json_ld_type(obj, "Product")) {
+                Some(product) => (*product, product.get("offers").map(Self::json_ld_nodes).unwrap_or_default()),
+                None => nodes.iter()
+                    .filter(|obj| Self::has_json_ld_type(obj, "Offer"))
+                    .find_map(|offer| Some((offer.get("itemOffered")?.as_object()?, vec![*offer])))?,
+            };
+            let text = |key: &str| product.get(key).and_then(Self::json_ld_text);
+
+            let priced = offers.iter().find_map(|offer| Some((*offer, Self::json_ld_offer_price(offer)?)));
+            let availability = priced.as_ref().map(|(offer, _)| *offer)
+                .or_else(|| offers.first().copied())
+                .and_then(|offer| offer.get("availability"))
+                .and_then(Self::json_ld_text)
+                .and_then(|v| Self::schema_term(&v));
+            let (price, currency) = priced.map(|(_, (price, currency))| (Some(price), currency)).unwrap_or_default();
+            let (rating, review_count) = Self::json_ld_rating(product);
+
+            Some(ProductData {
+                name: text("name"),
+                price,
+                currency,
+                availability,
+                sku: text("sku").or_else(|| text("mpn")),
+                brand: text("brand"),
+                rating,
+                review_count,
+            })
+        }
+
+        // (price, currency) of an Offer: price, else AggregateOffer lowPrice, else its
+        // priceSpecification. priceCurrency wins over a symbol in the price text.
+        fn json_ld_offer_price(offer: &serde_json::Map<String, serde_json::Value>) -> Option<(f64, Option<String>)> {
+            let spec = offer.get("priceSpecification").and_then(|s| Self::json_ld_nodes(s).into_iter().next());
+            let raw = offer.get("price")
+                .or_else(|| offer.get("lowPrice"))
+                .or_else(|| spec?.get("price"))
+                .and_then(Self::json_ld_text)?;
+            let (price, symbol_currency) = Self::parse_price(&raw)?;
+            let currency = offer.get("priceCurrency")
+                .or_else(|| spec?.get("priceCurrency"))
+                .and_then(Self::json_ld_text)
+                .map(|c| c.to_uppercase())
+                .or(symbol_currency);
+            Some((price, currency))
+        }
+
+        fn get_microdata_product(&self) -> Option<ProductData> {
+            let scope = self.microdata_scopes(&["/product"]).next()?;
+            // Names inside nested items (brand, review, seller) are not the product's name
+            let nested: HashSet<u32> = scope.query_selector(self.parser, "[itemscope]")?
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                .filter_map(|tag| tag.query_selector(self.parser, "[itemprop]"))
+                .flatten()
+                .map(|handle| handle.get_inner())
+                .collect();
+
+            let mut product = ProductData::default();
+            // Rating and count come from the product's AggregateRating only; nested Review
+            // items carry ratingValue props of their own
+            if let Some(rating_scope) = scope.query_selector(self.parser, "[itemscope]")?
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                .find(|tag| Self::microdata_itemtype(tag).map(|t| t.ends_with("/aggregaterating")).unwrap_or(false))
+            {
+                for prop_tag in rating_scope.query_selector(self.parser, "[itemprop]").into_iter().flatten()
+                    .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                {
+                    let Some(props) = prop_tag.attributes().get("itemprop").flatten() else { continue };
+                    for prop in props.as_utf8_str().split_whitespace() {
+                        match prop {
+                            "ratingValue" if product.rating.is_none() => {
+                                product.rating = self.microdata_value(prop_tag).and_then(|v| Self::parse_rating(&v));
+                            }
+                            "reviewCount" => product.review_count = self.microdata_value(prop_tag).and_then(|v| Self::parse_count(&v)),
+                            "ratingCount" if product.review_count.is_none() => {
+                                product.review_count = self.microdata_value(prop_tag).and_then(|v| Self::parse_count(&v));
+                            }
+                            _ => {}
                         }
                     }
                 }
             }
-            None
+
+            let mut symbol_currency = None;
+            for (handle, prop_tag) in scope.query_selector(self.parser, "[itemprop]")?
+                .filter_map(|handle| Some((handle, handle.get(self.parser)?.as_tag()?)))
+            {
+                let Some(props) = prop_tag.attributes().get("itemprop").flatten() else { continue };
+                for prop in props.as_utf8_str().split_whitespace() {
+                    match prop {
+                        "name" if product.name.is_none() && !nested.contains(&handle.get_inner()) => {
+                            product.name = self.microdata_value(prop_tag);
+                        }
+                        "price" | "lowPrice" if product.price.is_none() => {
+                            if let Some((price, currency)) = self.microdata_value(prop_tag).and_then(|v| Self::parse_price(&v)) {
+                                product.price = Some(price);
+                                symbol_currency = currency;
+                            }
+                        }
+                        "priceCurrency" if product.currency.is_none() => {
+                            product.currency = self.microdata_value(prop_tag).map(|c| c.to_uppercase());
+                        }
+                        // Usually <link itemprop="availability" href="https://schema.org/InStock">
+                        "availability" if product.availability.is_none() => {
+                            product.availability = prop_tag.attributes().get("href").flatten()
+                                .map(|href| href.as_utf8_str().to_string())
+                                .or_else(|| self.microdata_value(prop_tag))
+                                .and_then(|v| Self::schema_term(&v));
+                        }
+                        "sku" if product.sku.is_none() => product.sku = self.microdata_value(prop_tag),
+                        "brand" if product.brand.is_none() => product.brand = self.microdata_value(prop_tag),
+                        _ => {}
+                    }
+                }
+            }
+            product.currency = product.currency.or(symbol_currency);
+
+            Some(product)
+        }
+
+        // Price text to (amount, currency from a symbol or ISO code in the text).
+        // "1,299.00", "1.299,00" and "1 299,00" all read as 1299.0; a lone separator
+        // followed by exactly three digits is a thousands separator unless the integer
+        // part is zero ("0.500" dinars).
+        fn parse_price(raw: &str) -> Option<(f64, Option<String>)> {
+            let raw = raw.trim();
+            if let Ok(price) = raw.parse::<f64>() {
+                return Some((price, None)).filter(|(p, _)| p.is_finite() && *p >= 0.0);
+            }
+
+            let currency = CURRENCY_SYMBOLS.iter()
+                .find(|(symbol, _)| raw.contains(symbol))
+                .map(|(_, code)| code.to_string())
+                .or_else(|| raw.split(|c: char| !c.is_ascii_alphabetic())
+                    .find(|word| CURRENCY_CODES.contains(word))
+                    .map(str::to_string));
+
+            let number: String = raw.chars()
+                .skip_while(|c| !c.is_ascii_digit())
+                .take_while(|c| c.is_ascii_digit() || matches!(c, ',' | '.' | ' ' | '\u{a0}' | '\''))
+                .filter(|c| !matches!(c, ' ' | '\u{a0}' | '\''))
+                .collect();
+            let number = match number.rfind([',', '.']) {
+                Some(pos) => {
+                    let separator = &number[pos..pos + 1];
+                    let zero_integer = number[..pos].chars().all(|c| matches!(c, '0' | ',' | '.'));
+                    let is_decimal = if number.contains(',') && number.contains('.') {
+                        true
+                    } else {
+                        number.matches(separator).count() == 1 && (zero_integer || number.len() - pos - 1 != 3)
+                    };
+                    if is_decimal {
+                        format!("{}.{}", number[..pos].replace([',', '.'], ""), &number[pos + 1..])
+                    } else {
+                        number.replace([',', '.'], "")
+                    }
+                }
+                None => number,
+            };
+
+            number.parse::<f64>().ok().map(|price| (price, currency))
+        }
+
+        // Last segment of a schema.org enumeration URL: "https://schema.org/InStock" -> "InStock"
+        fn schema_term(value: &str) -> Option<String> {
+            let term = value.trim().trim_end_matches('/').rsplit(['/', ':']).next()?.trim();
+            if term.is_empty() { None } else { Some(term.to_string()) }
+        }
+
+        /// True when the page declares itself a Q&A or forum thread (schema.org QAPage,
+        /// Question or DiscussionForumPosting in JSON-LD or microdata)
+        pub fn is_thread_page(&self) -> bool {
+            let json_ld = self.json_ld_blocks.iter()
+                .flat_map(Self::json_ld_nodes)
+                .any(|obj| ["QAPage", "Question", "DiscussionForumPosting"].iter().any(|t| Self::has_json_ld_type(obj, t)));
+            json_ld || self.microdata_scopes(&["/qapage", "/question", "/discussionforumposting"]).next().is_some()
+        }
+
+        /// Posts of a forum or Q&A thread in document order: the question / original post
+        /// first, then answers and replies. JSON-LD is preferred, then schema.org microdata,
+        /// then the post containers of common forum engines.
+        pub fn get_thread(&self) -> Vec<ThreadPost> {
+            let posts = self.get_json_ld_thread();
+            if !posts.is_empty() {
+                return posts;
+            }
+            let posts = self.get_microdata_thread();
+            if !posts.is_empty() {
+                return posts;
+            }
+            self.get_selector_thread()
+        }
+
+        fn get_json_ld_thread(&self) -> Vec<ThreadPost> {
+            let mut posts = Vec::new();
+            let nodes: Vec<_> = self.json_ld_blocks.iter().flat_map(Self::json_ld_nodes).collect();
+            let root = nodes.iter()
+                .find(|obj| Self::has_json_ld_type(obj, "QAPage"))
+                .and_then(|page| page.get("mainEntity"))
+                .and_then(|entity| match entity {
+                    serde_json::Value::Array(items) => items.first()?.as_object(),
+                    other => other.as_object(),
+                })
+                .or_else(|| nodes.iter()
+                    .find(|obj| Self::has_json_ld_type(obj, "Question") || Self::has_json_ld_type(obj, "DiscussionForumPosting"))
+                    .copied());
+            let Some(root) = root else { return posts };
+
+            let role = if Self::has_json_ld_type(root, "Question") { "question" } else { "post" };
+            posts.extend(Self::json_ld_post(root, role));
+            for (key, role) in [("acceptedAnswer", "answer"), ("suggestedAnswer", "answer"), ("comment", "reply")] {
+                let replies = match root.get(key) {
+                    Some(serde_json::Value::Array(items)) => items.iter().collect(),
+                    Some(other) => vec![other],
+                    None => Vec::new(),
+                };
+                for reply in replies.into_iter().filter_map(|r| r.as_object()) {
+                    posts.extend(Self::json_ld_post(reply, role));
+                }
+            }
+            posts
+        }
+
+        fn json_ld_post(obj: &serde_json::Map<String, serde_json::Value>, role: &str) -> Option<ThreadPost> {
+            let text = obj.get("text")
+                .or_else(|| obj.get("articleBody"))
+                .and_then(Self::json_ld_text)
+                .or_else(|| obj.get("name").or_else(|| obj.get("headline")).and_then(Self::json_ld_text))?;
+            Some(ThreadPost {
+                role: role.to_string(),
+                author: obj.get("author").and_then(Self::extract_name_from_value),
+                date: ["dateCreated", "datePublished"].iter().find_map(|k| obj.get(*k).and_then(Self::json_ld_text)),
+                text,
+            })
+        }
+
+        fn get_microdata_thread(&self) -> Vec<ThreadPost> {
+            let mut posts: Vec<ThreadPost> = Vec::new();
+            for scope in self.microdata_scopes(&["/question", "/answer", "/comment", "/discussionforumposting"]) {
+                let itemtype = Self::microdata_itemtype(scope).unwrap_or_default();
+                let role = match itemtype.rsplit('/').next().unwrap_or("") {
+                    "question" => "question",
+                    "answer" => "answer",
+                    "comment" => "reply",
+                    _ => "post",
+                };
+                // Properties of nested items (an Answer inside its Question) belong to those items
+                let nested: HashSet<u32> = scope.query_selector(self.parser, "[itemscope]").into_iter().flatten()
+                    .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                    .flat_map(|inner| inner.query_selector(self.parser, "[itemprop]").into_iter().flatten())
+                    .map(|handle| handle.get_inner())
+                    .collect();
+                let mut post = ThreadPost { role: role.to_string(), author: None, date: None, text: String::new() };
+                for prop_tag in scope.query_selector(self.parser, "[itemprop]").into_iter().flatten()
+                    .filter(|handle| !nested.contains(&handle.get_inner()))
+                    .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                {
+                    let Some(props) = prop_tag.attributes().get("itemprop").flatten() else { continue };
+                    for prop in props.as_utf8_str().split_whitespace() {
+                        match prop {
+                            "text" | "articleBody" if post.text.is_empty() => {
+                                post.text = self.microdata_value(prop_tag).unwrap_or_default();
+                            }
+                            "author" if post.author.is_none() => post.author = self.microdata_value(prop_tag),
+                            "dateCreated" | "datePublished" if post.date.is_none() => post.date = self.microdata_value(prop_tag),
+                            _ => {}
+                        }
+                    }
+                }
+                if !post.text.is_empty() {
+                    posts.push(post);
+                }
+            }
+            posts
+        }
+
+        fn get_selector_thread(&self) -> Vec<ThreadPost> {
+            let mut posts: Vec<ThreadPost> = Vec::new();
+            for tag in self.dom.query_selector(THREAD_POST_SELECTORS).into_iter().flatten()
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+            {
+                let first = |selectors: &str| tag.query_selector(self.parser, selectors)
+                    .and_then(|mut iter| iter.next())
+                    .and_then(|h| h.get(self.parser).and_then(|n| n.as_tag()));
+                let text = first(THREAD_BODY_SELECTORS)
+                    .and_then(|body| self.microdata_value(body))
+                    .or_else(|| self.microdata_value(tag));
+                let Some(text) = text else { continue };
+
+                let class = tag.attributes().get("class").flatten()
+                    .map(|c| c.as_utf8_str().to_lowercase())
+                    .unwrap_or_default();
+                let role = if class.split_whitespace().any(|c| c == "question") {
+                    "question"
+                } else if class.split_whitespace().any(|c| c == "answer") {
+                    "answer"
+                } else if posts.is_empty() {
+                    "post"
+                } else {
+                    "reply"
+                };
+                // Stack Exchange's relative times carry the absolute date in their title
+                let date = first("time").and_then(|time| self.microdata_value(time))
+                    .or_else(|| first(".relativetime").and_then(|t| t.attributes().get("title").flatten()).map(|t| t.as_utf8_str().trim().to_string()));
+                posts.push(ThreadPost {
+                    role: role.to_string(),
+                    // Stack Exchange's user card also holds reputation; its profile link is the name
+                    author: first(".user-details")
+                        .and_then(|card| card.query_selector(self.parser, "a")?.next()?.get(self.parser)?.as_tag())
+                        .or_else(|| first(THREAD_AUTHOR_SELECTORS))
+                        .and_then(|author| self.microdata_value(author)),
+                    date,
+                    text,
+                });
+            }
+            posts
+        }
+
+        // Microdata item scopes whose itemtype ends with one of `suffixes` (lowercase)
+        fn microdata_scopes<'s>(&'s self, suffixes: &'s [&'s str]) -> impl Iterator<Item = &'s tl::HTMLTag<'a>> + 's {
+            self.dom.query_selector("[itemscope]").into_iter().flatten()
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+                .filter(move |tag| Self::microdata_itemtype(tag)
+                    .map(|t| suffixes.iter().any(|s| t.ends_with(s)))
+                    .unwrap_or(false))
+        }
+
+        // Lowercased itemtype; tl drops the first character of an attribute that follows
+        // a valueless one, so `itemscope itemtype="..."` surfaces as "temtype"
+        fn microdata_itemtype(tag: &tl::HTMLTag) -> Option<String> {
+            tag.attributes().iter()
+                .find(|(name, _)| name.len() >= 7 && "itemtype".ends_with(name.as_ref()))
+                .and_then(|(_, value)| value.map(|t| t.trim().to_lowercase()))
+        }
+
+        // URL of a JSON-LD image: a string, an ImageObject, or the first of an array
+        fn json_ld_image_url(value: &serde_json::Value) -> Option<String> {
+            match value {
+                serde_json::Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+                serde_json::Value::Object(obj) => obj.get("url").or_else(|| obj.get("contentUrl")).and_then(Self::json_ld_image_url),
+                serde_json::Value::Array(items) => items.iter().find_map(Self::json_ld_image_url),
+                _ => None,
+            }
+        }
+
+        fn get_microdata_recipe(&self, parse_duration: &impl Fn(&str) -> Option<u64>) -> Option<RecipeData> {
+            let scope = self.microdata_scopes(&["/recipe"]).next()?;
+
+            let mut recipe = RecipeData::default();
+            for prop_tag in scope.query_selector(self.parser, "[itemprop]")?
+                .filter_map(|handle| handle.get(self.parser).and_then(|n| n.as_tag()))
+            {
+                let Some(props) = prop_tag.attributes().get("itemprop").flatten() else { continue };
+                for prop in props.as_utf8_str().split_whitespace() {
+                    match prop {
+                        "name" if recipe.name.is_none() => recipe.name = self.microdata_value(prop_tag),
+                        "recipeIngredient" | "ingredients" => recipe.ingredients.extend(self.microdata_value(prop_tag)),
+                        "recipeInstructions" => recipe.instructions.extend(self.list_items_or_text(prop_tag)),
+                        "prepTime" => recipe.prep_time = self.microdata_value(prop_tag).and_then(|d| parse_duration(&d)),
+                        "cookTime" => recipe.cook_time = self.microdata_value(prop_tag).and_then(|d| parse_duration(&d)),
+                        "totalTime" => recipe.total_time = self.microdata_value(prop_tag).and_then(|d| parse_duration(&d)),
+                        "recipeYield" if recipe.recipe_yield.is_none() => recipe.recipe_yield = self.microdata_value(prop_tag),
+                        "ratingValue" if recipe.rating.is_none() => {
+                            recipe.rating = self.microdata_value(prop_tag).and_then(|v| Self::parse_rating(&v));
+                        }
+                        nutrient if NUTRITION_PROPERTIES.contains(&nutrient) => {
+                            if let Some(value) = self.microdata_value(prop_tag) {
+                                recipe.nutrition.insert(nutrient.to_string(), value);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            Some(recipe)
+        }
+
+        fn get_h_recipe(&self, parse_duration: &impl Fn(&str) -> Option<u64>) -> Option<RecipeData> {
+            let root = self.dom.query_selector(".h-recipe")?
+                .next()?
+                .get(self.parser)?
+                .as_tag()?;
+            let tags = |selector: &str| root.query_selector(self.parser, selector)
+                .map(|iter| iter.filter_map(|h| h.get(self.parser).and_then(|n| n.as_tag())).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            Some(RecipeData {
+                name: tags(".p-name").first().and_then(|tag| self.microdata_value(tag)),
+                ingredients: tags(".p-ingredient").iter().filter_map(|tag| self.microdata_value(tag)).collect(),
+                instructions: tags(".e-instructions").iter().flat_map(|tag| self.list_items_or_text(tag)).collect(),
+                prep_time: None,
+                cook_time: None,
+                total_time: tags(".dt-duration").first()
+                    .and_then(|tag| self.microdata_value(tag))
+                    .and_then(|d| parse_duration(&d)),
+                recipe_yield: tags(".p-yield").first().and_then(|tag| self.microdata_value(tag)),
+                servings: None,
+                // p-nutrition is free text; keep "Label: value" entries
+                nutrition: tags(".p-nutrition").iter()
+                    .filter_map(|tag| self.microdata_value(tag))
+                    .filter_map(|text| {
+                        let (label, value) = text.split_once(':')?;
+                        Some((label.trim().to_lowercase(), value.trim().to_string()))
+                    })
+                    .filter(|(label, value)| !label.is_empty() && !value.is_empty())
+                    .collect(),
+                // h-recipe has no rating property
+                rating: None,
+            })
+        }
+
+        // Machine-readable value of a microdata/microformat element: content or datetime
+        // attribute first, then its whitespace-normalized text
+        fn microdata_value(&self, tag: &tl::HTMLTag) -> Option<String> {
+            let attrs = tag.attributes();
+            let value = attrs.get("content").flatten()
+                .or_else(|| attrs.get("datetime").flatten())
+                .map(|v| v.as_utf8_str().to_string())
+                .unwrap_or_else(|| tag.inner_text(self.parser).to_string());
+            let value = if self.normalize_structured_data {
+                FastCleaner::normalize_value_text(&value)
+            } else {
+                value.split_whitespace().collect::<Vec<_>>().join(" ")
+            };
+            if value.is_empty() { None } else { Some(value) }
+        }
+
+        // One entry per <li> when the element holds a list, otherwise its text
+        fn list_items_or_text(&self, tag: &tl::HTMLTag) -> Vec<String> {
+            let items: Vec<String> = tag.query_selector(self.parser, "li")
+                .map(|iter| iter
+                    .filter_map(|h| h.get(self.parser).and_then(|n| n.as_tag()))
+                    .filter_map(|li| self.microdata_value(li))
+                    .collect())
+                .unwrap_or_default();
+            if items.is_empty() {
+                self.microdata_value(tag).into_iter().collect()
+            } else {
+                items
+            }
+        }
+
+
+        /// Raw `lang` attribute of the <html> element
+        pub fn get_html_lang(&self) -> Option<String> {
+            self.dom.query_selector("html")
+                .and_then(|mut iter| iter.next())
+                .and_then(|node| node.get(self.parser))
+                .and_then(|node| node.as_tag())
+                .and_then(|tag| tag.attributes().get("lang").flatten().map(|v| v.as_utf8_str().trim().to_string()))
+                .filter(|lang| !lang.is_empty())
+        }
+
+        pub fn get_og_locale(&self) -> Option<String> {
+            self.meta_map.get("og:locale").map(|l| l.trim().to_string()).filter(|l| !l.is_empty())
+        }
+
+        /// hreflang of the alternate link that points back at this page. x-default
+        /// names no locale, so it never counts as a self-reference.
+        pub fn get_self_hreflang(&self, page_url: &str, resolve_url: impl Fn(&str) -> String) -> Option<String> {
+            let same_page = |a: &str, b: &str| a.trim_end_matches('/') == b.trim_end_matches('/');
+
+            self.link_nodes.iter()
+                .filter_map(|node| node.get(self.parser).and_then(|n| n.as_tag()))
+                .find_map(|tag| {
+                    let attrs = tag.attributes();
+                    let rel = attrs.get("rel").flatten()?.as_utf8_str().to_lowercase();
+                    let hreflang = attrs.get("hreflang").flatten()?.as_utf8_str().trim().to_string();
+                    let href = attrs.get("href").flatten()?.as_utf8_str().to_string();
+                    let is_self = rel.split_whitespace().any(|r| r == "alternate")
+                        && !hreflang.eq_ignore_ascii_case("x-default")
+                        && same_page(&resolve_url(&href), page_url);
+                    is_self.then_some(hreflang)
+                })
+        }
+
+        /// AMP counterpart of the page (<link rel="amphtml">), resolved
+        pub fn get_amphtml_url(&self, resolve_url: impl Fn(&str) -> String) -> Option<String> {
+            self.rel_links("amphtml").next().map(|(_, href)| resolve_url(&href))
+        }
+
+        /// (hreflang, resolved URL) of every <link rel="alternate" hreflang>, x-default included
+        pub fn get_hreflang_alternates(&self, resolve_url: impl Fn(&str) -> String) -> Vec<(String, String)> {
+            self.rel_links("alternate")
+                .filter_map(|(tag, href)| {
+                    let hreflang = tag.attributes().get("hreflang").flatten()?.as_utf8_str().trim().to_string();
+                    (!hreflang.is_empty()).then(|| (hreflang, resolve_url(&href)))
+                })
+                .collect()
+        }
+
+        /// Separate mobile URL: a rel="alternate" link whose media query targets small
+        /// screens or handhelds, resolved
+        pub fn get_mobile_url(&self, resolve_url: impl Fn(&str) -> String) -> Option<String> {
+            self.rel_links("alternate")
+                .find(|(tag, _)| tag.attributes().get("media").flatten()
+                    .map(|media| {
+                        let media = media.as_utf8_str().to_lowercase();
+                        media.contains("max-width") || media.contains("handheld")
+                    })
+                    .unwrap_or(false))
+                .map(|(_, href)| resolve_url(&href))
+        }
+
+        // <link> tags whose rel list contains `rel`, with their non-empty href
+        fn rel_links<'s>(&'s self, rel: &'s str) -> impl Iterator<Item = (&'s tl::HTMLTag<'a>, String)> + 's {
+            self.link_nodes.iter()
+                .filter_map(|node| node.get(self.parser).and_then(|n| n.as_tag()))
+                .filter_map(move |tag| {
+                    let attrs = tag.attributes();
+                    let rels = attrs.get("rel").flatten()?.as_utf8_str().to_lowercase();
+                    if !rels.split_whitespace().any(|r| r == rel) {
+                        return None;
+                    }
+                    let href = attrs.get("href").flatten()?.as_utf8_str().trim().to_string();
+                    (!href.is_empty()).then_some((tag, href))
+                })
+        }
+
+        /// The canonical URL resolved against `base_url` whenever a canonical link exists,
+        /// including self-referential ones; None only when the page declares none
+        pub fn get_canonical_url(&self, base_url: &str) -> Option<String> {
+            let tag = self.canonical_node?.get(self.parser)?.as_tag()?;
+            let href = tag.attributes().get("href").flatten()?.as_utf8_str().trim().to_string();
+            if href.is_empty() {
+                return None;
+            }
+
+            match url::Url::parse(base_url) {
+                Ok(base) => Some(base.join(&href).map(|u| u.to_string()).unwrap_or(href)),
+                Err(_) => Some(href),
+            }
+        }
+
+        /// True when two URLs name the same page, ignoring fragments and a trailing slash
+        pub fn is_same_page(a: &str, b: &str) -> bool {
+            let normalize = |raw: &str| match url::Url::parse(raw) {
+                Ok(mut parsed) => {
+                    parsed.set_fragment(None);
+                    parsed.to_string().trim_end_matches('/').to_string()
+                }
+                Err(_) => raw.trim().trim_end_matches('/').to_string(),
+            };
+            normalize(a) == normalize(b)
         }
 
 
         
-        // Helper function for content categorization (unchanged from original)
-        pub fn get_content_categories(content: &str) -> Vec<String> {
+        /// Snowball algorithm for an ISO-639-1 language code, English when unsupported
+        pub fn stemmer_algorithm_for(language: Option<&str>) -> Algorithm {
+            let code = language.unwrap_or("en").to_lowercase();
+            match code.split(['-', '_']).next().unwrap_or("en") {
+                "ar" => Algorithm::Arabic,
+                "da" => Algorithm::Danish,
+                "nl" => Algorithm::Dutch,
+                "fi" => Algorithm::Finnish,
+                "fr" => Algorithm::French,
+                "de" => Algorithm::German,
+                "el" => Algorithm::Greek,
+                "hu" => Algorithm::Hungarian,
+                "it" => Algorithm::Italian,
+                "no" | "nb" | "nn" => Algorithm::Norwegian,
+                "pt" => Algorithm::Portuguese,
+                "ro" => Algorithm::Romanian,
+                "ru" => Algorithm::Russian,
+                "es" => Algorithm::Spanish,
+                "sv" => Algorithm::Swedish,
+                "ta" => Algorithm::Tamil,
+                "tr" => Algorithm::Turkish,
+                _ => Algorithm::English,
+            }
+        }
+
+        /// Up to three categories whose keyword score reaches `min_score`. Each distinct
+        /// matched keyword scores 1, multi-word keywords 2 as they are far less incidental.
+        /// Text and vocabulary are stemmed with `language`'s algorithm (English when unsupported).
+        pub fn get_content_categories(content: &str, language: Option<&str>, min_score: usize) -> Vec<String> {
             let mut categories = Vec::new();
-        let re = Regex::new(r"\b\w+\b").unwrap();
-            let tokens: Vec<String> = re
+            let tokens: Vec<String> = WORD_TOKEN
                 .find_iter(content)
-                .map(|m| m.as_str().to_lowercase())
+                .map(|m| FastCleaner::fold_case(m.as_str()))
                 .collect();
 
             let stopwords = [
             "the", "and", "a", "an", "of", "to", "in", "for", "on", "with", "is", "it", "that", 
             "this", "at", "by", "from", "as", "are", "be", "or", "was", "were", "has", "had", "have"
         ];
-            let stemmer = Stemmer::create(Algorithm::English);
 
-            // Create stemmed ngrams (unigrams + bigrams)
-            let mut ngrams = HashSet::new();
-            for i in 0..tokens.len() {
-                let word = &tokens[i];
-                if stopwords.contains(&word.as_str()) {
-                    continue;
+            let algorithm = Self::stemmer_algorithm_for(language);
+
+            CATEGORY_STEMMER.with(|cached| {
+                let mut cached = cached.borrow_mut();
+                if cached.algorithm != algorithm {
+                    *cached = CategoryStemmer::new(algorithm);
                 }
-                let stemmed = stemmer.stem(word).to_string();
-                ngrams.insert(stemmed.clone());
 
-                if i + 1 < tokens.len() {
-                    let next_word = &tokens[i + 1];
-                    if !stopwords.contains(&next_word.as_str()) {
-                        let next_stemmed = stemmer.stem(next_word).to_string();
-                        let bigram = format!("{} {}", stemmed, next_stemmed);
-                        ngrams.insert(bigram);
+                // Create stemmed ngrams (unigrams + bigrams)
+                let mut ngrams = HashSet::new();
+                for i in 0..tokens.len() {
+                    let word = &tokens[i];
+                    if stopwords.contains(&word.as_str()) {
+                        continue;
+                    }
+                    let stemmed = cached.stemmer.stem(word).to_string();
+                    ngrams.insert(stemmed.clone());
+
+                    if i + 1 < tokens.len() {
+                        let next_word = &tokens[i + 1];
+                        if !stopwords.contains(&next_word.as_str()) {
+                            let next_stemmed = cached.stemmer.stem(next_word).to_string();
+                            let bigram = format!("{} {}", stemmed, next_stemmed);
+                            ngrams.insert(bigram);
 
+                        }
                     }
                 }
-            }
 
-            let category_keywords: Vec<(&str, Vec<&str>)> = vec![
-            ("news", vec![
-                "news", "breaking", "update", "report", "headline", "journal", "media", "press",
-                "announcement", "current", "daily news", "broadcast", "bulletin", "article", "coverage"
-            ]),
-            ("sports", vec![
-                "football", "soccer", "basketball", "tennis", "cricket", "match", "tournament", "goal",
-                "score", "league", "athlete", "olympics", "championship", "competition", "playoff",
-                "coach", "team", "game", "sportsmanship", "player"
-            ]),
-            ("finance", vec![
-                "stocks", "market", "investment", "finance", "economy", "bitcoin", "trading", "crypto",
-                "banking", "fund", "portfolio", "mutual fund", "currency", "inflation", "deficit",
-                "revenue", "capital", "dividend", "savings", "insurance"
-            ]),
-            ("health", vec![
-                "health", "medicine", "wellness", "fitness", "disease", "nutrition", "exercise",
-                "mental health", "medical", "therapy", "diet", "treatment", "hospital", "doctor",
-                "clinic", "vaccine", "infection", "immune", "prevention", "rehabilitation"
-            ]),
-            ("entertainment", vec![
-                "movie", "film", "tv", "music", "celebrity", "show", "concert", "series", "album",
-                "entertainment", "theater", "drama", "comedy", "festival", "artist", "actor", "actress",
-                "performance", "pop culture"
-            ]),
-            ("science", vec![
-                "research", "experiment", "physics", "chemistry", "biology", "scientist", "study",
-                "discovery", "laboratory", "experiment", "theory", "analysis", "observation",
-                "scientific", "innovation", "space", "astronomy", "genetics", "geology", "climate"
-            ]),
-            ("travel", vec![
-                "travel", "tourism", "destination", "flight", "hotel", "journey", "adventure", "trip",
-                "vacation", "holiday", "explore", "sightseeing", "cruise", "itinerary", "backpacking",
-                "resort", "beach", "mountain", "culture", "transportation"
-            ]),
-            ("food", vec![
-                "food", "cuisine", "recipe", "dish", "restaurant", "meal", "dining", "chef",
-                "ingredient", "gourmet", "taste", "baking", "cooking", "snack", "drink",
-                "beverage", "dessert", "nutrition", "vegan", "organic"
-            ]),
-            ("fashion", vec![
-                "fashion", "style", "clothing", "apparel", "designer", "trend", "runway",
-                "collection", "brand", "outfit", "accessory", "model", "vogue", "couture",
-                "textile", "footwear", "jewelry", "cosmetics", "hairstyle", "makeup"
-            ]),
-            ("education", vec![
-                "education", "learning", "school", "college", "university", "course",
-                "student", "teacher", "lecture", "curriculum", "study", "training",
-                "knowledge", "academy", "classroom", "exam", "scholarship", "tutorial", "online course", "degree"
-            ])
-        ].iter().cloned().collect();
+                // Count keyword matches
+                let mut category_scores: Vec<(&str, usize)> = cached.vocabulary.iter()
+                    .map(|(category, keywords)| {
+                        let score = keywords.iter()
+                            .filter(|(kw, _)| ngrams.contains(kw))
+                            .map(|(_, weight)| weight)
+                            .sum();
+                        (*category, score)
+                    })
+                    .filter(|(_, score)| *score > 0 && *score >= min_score)
+                    .collect();
+
+                // Sort by match count descending
+                category_scores.sort_by(|a, b| b.1.cmp(&a.1));
+
+                // Add top categories until we reach max 3
+                for (cat, _) in category_scores {
+                    if categories.len() >= 3 {
+                        break;
+                    }
+                    if !categories.contains(&cat.to_string()) {
+                        categories.push(cat.to_string());
+                    }
+                }
+            });
 
-            // Count keyword matches
-            let mut category_scores: Vec<(&str, usize)> = category_keywords.iter()
-                .map(|(category, keywords)| {
-                    let score = keywords.iter().filter(|kw| ngrams.contains(&kw.to_string())).count();
-                    (*category, score)
-                })
-                .filter(|(_, score)| *score > 0)
-                .collect();
+            categories
+        }
+    }
 
-            // Sort by match count descending
-            category_scores.sort_by(|a, b| b.1.cmp(&a.1));
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-            // Add top categories until we reach max 3
-            for (cat, _) in category_scores {
-                if categories.len() >= 3 {
-                    break;
-                }
-                if !categories.contains(&cat.to_string()) {
-                    categories.push(cat.to_string());
-                }
+        fn with_extractor<T>(html: &str, check: impl FnOnce(&MetadataExtractor) -> T) -> T {
+            let dom = tl::parse(html, ParserOptions::default()).unwrap();
+            let extractor = MetadataExtractor::new(&dom, dom.parser());
+            check(&extractor)
+        }
+
+        #[test]
+        fn yoast_graph_members_supply_author_and_dates() {
+            let html = r#"<html><head><script type="application/ld+json">{
+                "@context": "https://schema.org",
+                "@graph": [
+                    {"@type": "WebSite", "@id": "https://example.com/#website", "name": "Example"},
+                    {"@type": "Article", "@id": "https://example.com/post/#article",
+                     "headline": "Post", "author": {"@id": "https://example.com/#/schema/person/1"},
+                     "datePublished": "2025-08-25T09:00:00+00:00", "dateModified": "2025-08-26T10:30:00+00:00"},
+                    {"@type": "Person", "@id": "https://example.com/#/schema/person/1", "name": "Jane Doe"}
+                ]
+            }</script></head><body></body></html>"#;
+            with_extractor(html, |extractor| {
+                assert_eq!(extractor.get_authors(), vec!["Jane Doe".to_string()]);
+                let (published, modified) = extractor.get_dates();
+                assert_eq!(published.as_deref(), Some("2025-08-25T09:00:00+00:00"));
+                assert_eq!(modified.as_deref(), Some("2025-08-26T10:30:00+00:00"));
+            });
+        }
+
+        #[test]
+        fn recipe_rating_comes_from_aggregate_rating() {
+            let html = r#"<html><head><script type="application/ld+json">{
+                "@context": "https://schema.org", "@type": "Recipe", "name": "Lentil soup",
+                "recipeIngredient": ["1 cup lentils", "1 onion"],
+                "recipeInstructions": [{"@type": "HowToStep", "text": "Simmer for 30 minutes."}],
+                "cookTime": "PT30M", "recipeYield": "4 servings",
+                "aggregateRating": {"@type": "AggregateRating", "ratingValue": "4,6", "reviewCount": "212"}
+            }</script></head><body></body></html>"#;
+            with_extractor(html, |extractor| {
+                let recipe = extractor.get_recipe(|d| FastCleaner::new().parse_iso_duration(d)).unwrap();
+                assert_eq!(recipe.rating, Some(4.6));
+                assert_eq!(recipe.cook_time, Some(30 * 60));
+                assert_eq!(recipe.servings, Some(4));
+                assert_eq!(recipe.ingredients.len(), 2);
+            });
+        }
+
+        #[test]
+        fn review_count_falls_back_to_rating_count() {
+            let node = serde_json::json!({"aggregateRating": {"ratingValue": 4.2, "ratingCount": 87}});
+            let (value, count) = MetadataExtractor::json_ld_rating(node.as_object().unwrap());
+            assert_eq!(value, Some(4.2));
+            assert_eq!(count, Some(87));
+        }
+
+        #[test]
+        fn prices_split_into_amount_and_currency() {
+            let cases = [
+                ("$1,299.00", 1299.0, Some("USD")),
+                ("1.299,50 €", 1299.5, Some("EUR")),
+                ("KWD 0.500", 0.5, Some("KWD")),
+                ("19.99 NOW", 19.99, None),
+            ];
+            for (raw, amount, currency) in cases {
+                let (price, code) = MetadataExtractor::parse_price(raw).unwrap();
+                assert_eq!(price, amount, "{}", raw);
+                assert_eq!(code.as_deref(), currency, "{}", raw);
             }
+        }
 
-            categories
+        #[test]
+        fn microdata_product_reads_rating_from_its_aggregate_rating_scope() {
+            let html = r#"<html><body><div itemscope itemtype="https://schema.org/Product">
+                <h1 itemprop="name">Desk lamp</h1>
+                <div itemprop="offers" itemscope itemtype="https://schema.org/Offer">
+                    <span itemprop="price" content="49.90">49,90</span>
+                    <meta itemprop="priceCurrency" content="EUR">
+                </div>
+                <div itemprop="aggregateRating" itemscope itemtype="https://schema.org/AggregateRating">
+                    <span itemprop="ratingValue">4.4</span> from <span itemprop="reviewCount">1,234</span> reviews
+                </div>
+            </div></body></html>"#;
+            with_extractor(html, |extractor| {
+                let product = extractor.get_product().unwrap();
+                assert_eq!(product.name.as_deref(), Some("Desk lamp"));
+                assert_eq!(product.price, Some(49.9));
+                assert_eq!(product.currency.as_deref(), Some("EUR"));
+                assert_eq!(product.rating, Some(4.4));
+                assert_eq!(product.review_count, Some(1234));
+            });
+        }
+
+        #[test]
+        fn spanish_text_is_stemmed_with_the_spanish_algorithm() {
+            let algorithm = MetadataExtractor::stemmer_algorithm_for(Some("es-ES"));
+            assert_eq!(algorithm, Algorithm::Spanish);
+            let stemmer = Stemmer::create(algorithm);
+            assert_eq!(stemmer.stem("bibliotecas"), "bibliotec");
+            assert_eq!(stemmer.stem("corriendo"), "corr");
+            assert_eq!(stemmer.stem("económicos"), "econom");
+            assert_eq!(MetadataExtractor::stemmer_algorithm_for(Some("xx")), Algorithm::English);
+        }
+
+        #[test]
+        fn cached_stemmer_keeps_categories_stable_across_documents() {
+            let article = "Markets rallied on Friday after the central bank signalled a pause in rate rises. \
+                Investors moved money back into stocks, and the currency strengthened against the dollar. \
+                Analysts said inflation had cooled for a third month, easing pressure on savings and pension funds. \
+                The report, published by the finance ministry, also showed revenue from trading rose. \
+                Football clubs listed on the exchange gained too, as the league announced a new broadcast deal \
+                covering every match of the season.";
+            let expected = vec!["finance".to_string(), "news".to_string(), "sports".to_string()];
+            assert_eq!(MetadataExtractor::get_content_categories(article, Some("en"), 1), expected);
+            // Switching language rebuilds the cached stemmer; switching back must give the same result
+            MetadataExtractor::get_content_categories("Der Verein gewann das Spiel in der Liga", Some("de"), 1);
+            assert_eq!(MetadataExtractor::get_content_categories(article, Some("en"), 1), expected);
         }
+
+        #[test]
+        fn each_og_video_url_opens_its_own_media_object() {
+            let html = r#"<html><head>
+                <meta property="og:video" content="https://example.com/a.mp4">
+                <meta property="og:video:type" content="video/mp4">
+                <meta property="og:video:width" content="1280">
+                <meta property="og:video" content="https://example.com/b.webm">
+                <meta property="og:video:type" content="video/webm">
+                </head><body></body></html>"#;
+            with_extractor(html, |extractor| {
+                let media = extractor.get_og_media(|url| url.to_string());
+                assert_eq!(media.len(), 2);
+                assert_eq!(media[0].url, "https://example.com/a.mp4");
+                assert_eq!(media[0].mime_type.as_deref(), Some("video/mp4"));
+                assert_eq!(media[0].width, Some(1280));
+                assert_eq!(media[1].url, "https://example.com/b.webm");
+                assert_eq!(media[1].mime_type.as_deref(), Some("video/webm"));
+                assert_eq!(media[1].width, None);
+            });
+        }
+
+        #[test]
+        fn one_incidental_keyword_does_not_assign_a_category() {
+            let text = "The compiler release notes cover the new borrow checker and faster incremental builds; \
+                the team behind it thanked contributors.";
+            let min_score = ProcessingConfig::default().min_category_score;
+            assert_eq!(MetadataExtractor::get_content_categories(text, Some("en"), 1), vec!["sports".to_string()]);
+            assert!(MetadataExtractor::get_content_categories(text, Some("en"), min_score).is_empty());
+        }
+
+        #[test]
+        fn content_type_source_is_json_ld_for_declared_articles() {
+            let declared = r#"<html><head><script type="application/ld+json">{"@type": "Article", "headline": "Notes"}</script>
+                </head><body></body></html>"#;
+            with_extractor(declared, |extractor| {
+                let primary = &extractor.classify_content_type("https://example.com/notes")[0];
+                assert_eq!(primary.content_type, "article");
+                assert_eq!(primary.source, "json_ld");
+            });
+            let undeclared = "<html><head><title>Notes from the garden</title></head><body></body></html>";
+            with_extractor(undeclared, |extractor| {
+                let primary = &extractor.classify_content_type("https://example.com/notes")[0];
+                assert_eq!(primary.source, "heuristic");
+            });
+        }
+
+#[test]
+fn recipe_merges_json_ld_ingredients_with_microdata_instructions() {
+    let html = r#"<html><head><script type="application/ld+json">{
+        "@context": "https://schema.org", "@type": "Recipe", "name": "Flatbread",
+        "recipeIngredient": ["2 cups flour", "1 cup water"]
+    }</script></head><body>
+    <div itemscope itemtype="https://schema.org/Recipe">
+        <h1 itemprop="name">Flatbread</h1>
+        <ol itemprop="recipeInstructions"><li>Mix the dough.</li><li>Bake for 10 minutes.</li></ol>
+    </div></body></html>"#;
+    with_extractor(html, |extractor| {
+        let recipe = extractor.get_recipe(|d| FastCleaner::new().parse_iso_duration(d)).unwrap();
+        assert_eq!(recipe.ingredients, vec!["2 cups flour".to_string(), "1 cup water".to_string()]);
+        assert_eq!(recipe.instructions, vec!["Mix the dough.".to_string(), "Bake for 10 minutes.".to_string()]);
+    });
+}
     }
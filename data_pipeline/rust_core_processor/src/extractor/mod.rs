@@ -1,10 +1,11 @@
 pub mod metadata_extractor;
 pub mod optimized;
 pub mod main_content_extractor;
+pub mod app_state;
 pub use optimized::OptimizedExtractor;
 
 // Re-export for compatibility  
-pub fn extract_all_metadata(html: &str, base_url: &str) -> crate::types::ProcessedDocument {
-    let extractor = OptimizedExtractor::new();
+pub fn extract_all_metadata(html: &str, base_url: &str) -> Result<crate::types::ProcessedDocument, tl::ParseError> {
+    let extractor = OptimizedExtractor::with_config(crate::config::ProcessingConfig::default());
     extractor.extract_content(html, base_url)
 }
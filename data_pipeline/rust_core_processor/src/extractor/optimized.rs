@@ -1,25 +1,85 @@
 use std::collections::HashMap;
 use tl::{Parser, HTMLTag, Node};
 use regex::Regex;
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use chrono::{DateTime, NaiveDateTime, Utc, TimeZone, NaiveDate};
 use crate::types::*;
 use crate::cleaner::FastCleaner;
+use crate::phone;
+use crate::math;
+use crate::config::{AbbreviationMode, ProcessingConfig, ThreadMode};
+use crate::language_detector::{FastLanguageDetector, DEFAULT_MIN_CONFIDENCE};
 use std::collections::HashSet;
 use crate::extractor::metadata_extractor::MetadataExtractor;
-use crate::extractor::main_content_extractor::MainContentExtractor;
+use crate::extractor::main_content_extractor::{self, MainContentExtractor};
+use crate::extractor::app_state;
 
 pub struct OptimizedExtractor {
     // Precompiled regex patterns for performance
     api_pattern: Regex,
+    // Like tech_pattern, only ever run over `config.analysis_sample` of the content
     function_pattern: Regex,
     tech_pattern: Regex,
-    date_patterns: Vec<Regex>,
     url_pattern: Regex,
     email_pattern: Regex,
+    // Shared by per-heading clean-up, which would otherwise build one per heading
+    cleaner: FastCleaner,
+    config: ProcessingConfig,
 }
 
+// Generic navigation link text; shared by chunk noise filtering and anchor scoring
+const LINK_INDICATORS: &[&str] = &[
+    "click here", "read more", "learn more", "view all",
+    "home page", "contact us", "about us", "privacy policy",
+];
+
+// Outbound links kept per document; link-heavy hubs would otherwise dominate the output
+const MAX_LINKS: usize = 50;
+
+// Single-word anchors that say nothing about the link target
+const GENERIC_ANCHOR_WORDS: &[&str] = &["here", "more", "link", "this", "click", "go", "next", "previous", "details"];
+
+// Phrases that login/registration walls put in place of the article body
+const LOGIN_GATE_PHRASES: &[&str] = &[
+    "sign in to continue", "log in to continue", "login to continue", "sign in to read",
+    "log in to read", "please sign in", "please log in", "please login",
+    "you must be logged in", "you need to be logged in", "register to continue",
+    "register to read", "create an account to continue", "create a free account to",
+    "sign up to continue", "sign up to read", "members only", "for registered users only",
+];
+
+// Titles that open with an HTTP error status: "404", "Error 404 | Site", "410 - Gone"
+static ERROR_TITLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\W*(?:error\W*)?(?:404|410)\b").unwrap());
+
+// Elements news and magazine templates use for the standfirst under the headline
+const LEDE_SELECTORS: &[&str] = &[
+    ".standfirst", ".lede", ".lead", ".article-lead", ".dek", ".article-dek", ".intro", ".article-intro",
+    ".subheadline", ".summary", "[itemprop='description']",
+];
+
+// A first paragraph shorter than this is a dateline or caption, not a lede
+const MIN_LEAD_PARAGRAPH_WORDS: usize = 15;
+
+// Content types (rule names and JSON-LD @types) that get forum / Q&A thread extraction
+const THREAD_CONTENT_TYPES: &[&str] = &["faq", "qapage", "question", "discussionforumposting"];
+
+// Length of a description derived from the body when the metadata one repeats the title
+const DERIVED_DESCRIPTION_CHARS: usize = 160;
+
+// Filler vocabulary of the standard "Lorem ipsum" passage
+const LOREM_IPSUM_WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed",
+    "eiusmod", "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna", "aliqua",
+    "enim", "ad", "minim", "veniam", "quis", "nostrud", "exercitation", "ullamco", "laboris",
+    "nisi", "aliquip", "ex", "ea", "commodo", "consequat", "duis", "aute", "irure",
+];
+
+// Decorative glyphs that sites wrap around headings (breadcrumb arrows, bullets, permalinks)
+const HEADING_GLYPHS: &[char] = &['»', '«', '›', '‹', '•', '·', '|', '→', '←', '▸', '▶', '►', '◂', '◀', '¶', '§', '>', '<', '–', '—'];
+
 impl OptimizedExtractor {
-    pub fn new() -> Self {
+    pub fn with_config(config: ProcessingConfig) -> Self {
         // Technical content patterns (reduced for performance)
         let tech_keywords = [
             "api", "function", "class", "method", "algorithm", "code", "software", 
@@ -70,73 +130,247 @@ impl OptimizedExtractor {
         
         let tech_pattern_str = tech_keywords.join("|");
         
-        // Essential date patterns only
-        let date_patterns = vec![
-            Regex::new(r"\d{4}-\d{2}-\d{2}").unwrap(),       // YYYY-MM-DD
-            Regex::new(r"\d{2}/\d{2}/\d{4}").unwrap(),       // MM/DD/YYYY
-            Regex::new(r"\d{2}-\d{2}-\d{4}").unwrap(),       // MM-DD-YYYY
-            Regex::new(r"\d{2}\.\d{2}\.\d{4}").unwrap(),     // DD.MM.YYYY
-            Regex::new(r"\d{4}/\d{2}/\d{2}").unwrap(),       // YYYY/MM/DD
-            Regex::new(r"\d{4}\.\d{2}\.\d{2}").unwrap(),     // YYYY.MM.DD
-            Regex::new(r"\d{2} \w+ \d{4}").unwrap(),         // DD Month YYYY (e.g., 25 August 2025)
-            Regex::new(r"\w+ \d{2}, \d{4}").unwrap(),        // Month DD, YYYY (e.g., August 25, 2025)
-            Regex::new(r"\d{1,2}/\d{1,2}/\d{2}").unwrap(),   // M/D/YY or MM/DD/YY
-            Regex::new(r"\d{1,2}-\d{1,2}-\d{2}").unwrap(),   // M-D-YY or MM-DD-YY
-            Regex::new(r"\d{1,2} \w{3} \d{4}").unwrap(),     // DD Mon YYYY (e.g., 25 Aug 2025)
-            Regex::new(r"\d{1,2}-\w{3}-\d{4}").unwrap(),     // DD-Mon-YYYY (e.g., 25-Aug-2025)
-            Regex::new(r"\w{3} \d{1,2}, \d{4}").unwrap(),    // Mon DD, YYYY (e.g., Aug 25, 2025)
-            Regex::new(r"\d{8}").unwrap(),                   // YYYYMMDD
-            Regex::new(r"\d{2}\s?/\s?\d{2}\s?/\s?\d{4}").unwrap(), // MM / DD / YYYY with optional spaces
-        ];
-
         Self {
             api_pattern: Regex::new(r"\b[A-Z][A-Za-z0-9_]*\.[A-Za-z0-9_]+\b").unwrap(),
-            function_pattern: Regex::new(r"\b[a-z_][a-z0-9_]*\(.*?\)\b").unwrap(),
+            function_pattern: Regex::new(r"\b[a-z_][a-z0-9_]*\([^()\n]*\)").unwrap(),
             tech_pattern: Regex::new(&format!(r"(?i)\b(?:{})\b", tech_pattern_str)).unwrap(),
-            date_patterns,
             url_pattern: Regex::new(r"https?://[^\s]+").unwrap(),
             email_pattern: Regex::new(r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b").unwrap(),
+            cleaner: FastCleaner::with_config(&config),
+            config,
         }
     }
 
-    pub fn extract_content(&self, html: &str, base_url: &str) -> ProcessedDocument {
-        let dom = tl::parse(html, tl::ParserOptions::default()).unwrap();
+    pub fn extract_content(&self, html: &str, base_url: &str) -> Result<ProcessedDocument, tl::ParseError> {
+        let dom = tl::parse(html, tl::ParserOptions::default())?;
         let parser = dom.parser();
         
         let mut document = ProcessedDocument::default();
-        let metadata_extractor = MetadataExtractor::new(&dom, parser);
-        let main_content_extractor = MainContentExtractor;
+        let metadata_extractor = MetadataExtractor::with_config(&dom, parser, &self.config);
+        let math_blocks = math::extract_math_blocks(&dom, parser);
+        let main_content_extractor = MainContentExtractor::new(&self.config).with_math_blocks(&math_blocks);
         
         // Extract all metadata using the cached extractor
         document.title = metadata_extractor.get_title().unwrap_or_default();
         document.description = metadata_extractor.get_description().unwrap_or_default();
         document.keywords = metadata_extractor.get_keywords();
-        document.content_type = metadata_extractor.get_content_type(base_url);
+        document.keyword_expansions = self.expand_keywords(&document.keywords);
+        let mut content_types = metadata_extractor.classify_content_type(base_url).into_iter();
+        if let Some(primary) = content_types.next() {
+            document.content_type = primary.content_type;
+            document.content_type_source = primary.source;
+            document.content_type_confidence = primary.score;
+        }
+        if self.config.content_type_alternatives {
+            document.content_type_alternatives = content_types.collect();
+        }
+        document.json_ld_blocks_skipped = metadata_extractor.json_ld_skipped();
         document.primary_image = metadata_extractor.get_primary_image(|s| self.resolve_url(s, base_url));
         document.favicon = metadata_extractor.get_favicon(|s| self.resolve_url(s, base_url));
-        document.author_name = metadata_extractor.get_author();
-        (document.published_date, document.modified_date) = 
-        metadata_extractor.get_dates();
+        document.theme_color = metadata_extractor.get_theme_color();
+        document.open_graph = metadata_extractor.get_open_graph().into_iter()
+            .map(|(key, values)| (key.trim_start_matches("og:").to_string(), values))
+            .collect();
+        document.twitter_cards = metadata_extractor.get_prefixed_meta("twitter:");
+        if self.config.responsive_images {
+            document.responsive_images = metadata_extractor.get_responsive_images(|s| self.resolve_url(s, base_url));
+        }
+        document.media = metadata_extractor.get_og_media(|s| self.resolve_url(s, base_url));
+        document.code_blocks = dom.query_selector("pre").into_iter().flatten()
+            .filter_map(|handle| handle.get(parser).and_then(|n| n.as_tag()))
+            .filter_map(|pre| main_content_extractor::code_block(pre, parser))
+            .collect();
+        document.tables = dom.query_selector("table").into_iter().flatten()
+            .filter_map(|handle| handle.get(parser).and_then(|n| n.as_tag()))
+            .filter_map(|table| main_content_extractor::table_info(table, parser))
+            .take(self.config.max_tables)
+            .collect();
+        document.links = self.extract_links(&dom, parser, base_url);
+        document.authors = metadata_extractor.get_authors();
+        document.author_name = document.authors.first().cloned()
+            .or_else(|| metadata_extractor.get_publisher_name());
+        if let Some(author) = document.authors.first() {
+            (document.author_bio, document.author_social_links) =
+                metadata_extractor.get_author_profile(author, |s| self.resolve_url(s, base_url));
+        }
+        if self.config.phone_numbers {
+            document.phone_numbers = phone::extract_phone_numbers(
+                &dom,
+                parser,
+                phone::infer_region(metadata_extractor.get_address_country(), base_url).as_deref(),
+            );
+        }
+        document.geo = metadata_extractor.get_geo();
+        document.news_section = metadata_extractor.get_news_section(base_url, &self.config.news_sections);
+        document.declared_word_count = metadata_extractor.get_declared_word_count();
+        // Dates leave the extractor ISO-8601 (UTC, "Z") or None, for strict date mappings
+        let (published_date, modified_date) = metadata_extractor.get_dates();
+        (document.published_date, document.modified_date) = (
+            published_date.and_then(|raw| self.normalize_date(raw, &mut document.warnings)),
+            modified_date.and_then(|raw| self.normalize_date(raw, &mut document.warnings)),
+        );
         document.canonical_url = metadata_extractor.get_canonical_url(base_url);
-        document.main_content = main_content_extractor.extract_main_content(&dom, parser);
-        document.content_categories = MetadataExtractor::get_content_categories(&document.main_content);
+        document.is_self_canonical = document.canonical_url.as_deref()
+            .map(|canonical| MetadataExtractor::is_same_page(canonical, base_url))
+            .unwrap_or(false);
+        if let Some((locale, confidence)) = FastLanguageDetector::resolve_locale(
+            metadata_extractor.get_html_lang().as_deref(),
+            metadata_extractor.get_og_locale().as_deref(),
+            metadata_extractor.get_self_hreflang(base_url, |s| self.resolve_url(s, base_url)).as_deref(),
+            base_url,
+        ) {
+            document.resolved_locale = Some(locale);
+            document.resolved_locale_confidence = confidence;
+        }
+        document.durations = self.normalize_durations(metadata_extractor.get_durations());
+        document.howto = metadata_extractor.get_howto(
+            |d| self.cleaner.parse_iso_duration(d),
+            |s| self.resolve_url(s, base_url),
+        );
+        if document.howto.is_some() {
+            document.content_type = "howto".to_string();
+            document.content_type_source = "json_ld".to_string();
+        }
+        document.product = metadata_extractor.get_product();
+        let run_thread = match self.config.thread_mode {
+            ThreadMode::Force => true,
+            ThreadMode::Auto => THREAD_CONTENT_TYPES.contains(&document.content_type.as_str())
+                || metadata_extractor.is_thread_page(),
+            ThreadMode::Off => false,
+        };
+        if run_thread {
+            document.thread = metadata_extractor.get_thread();
+        }
+        if document.content_type == "recipe" {
+            document.recipe = metadata_extractor.get_recipe(|d| self.cleaner.parse_iso_duration(d));
+        }
+        (document.main_content, document.content_region_offsets) =
+            main_content_extractor.extract_main_content_with_region(&dom, parser);
+        document.main_content_html = main_content_extractor.region_html();
+        if self.config.math_placeholders && !math_blocks.is_empty() {
+            document.main_content = math::tex_placeholders(&document.main_content, &math_blocks);
+        }
+        document.math_blocks = math_blocks.clone();
+        // Client-rendered apps ship a thin server body; recover the article from the app state
+        let dom_words = document.main_content.split_whitespace().count();
+        if dom_words < self.config.app_state_min_words {
+            if let Some(state_text) = app_state::extract_app_state_text(&dom, parser)
+                .filter(|text| text.split_whitespace().count() > dom_words)
+            {
+                document.main_content = state_text;
+                document.content_region_offsets = None;
+                document.main_content_html = None;
+                document.content_source = "app_state".to_string();
+            }
+        }
+        document.read_more_truncated =
+            main_content_extractor::read_more_without_continuation(&dom, parser, &document.main_content);
+        if self.config.collect_warnings {
+            if url::Url::parse(base_url).is_err() {
+                document.warnings.push("base_url_unparseable".to_string());
+            }
+            document.warnings.extend(
+                std::iter::repeat_n("json_ld_parse_failed".to_string(), metadata_extractor.json_ld_parse_failures())
+            );
+            if document.json_ld_blocks_skipped > 0 {
+                document.warnings.push(format!("json_ld_blocks_skipped:{}", document.json_ld_blocks_skipped));
+            }
+            if main_content_extractor.used_body_fallback() {
+                document.warnings.push("body_fallback_used".to_string());
+            }
+            if metadata_extractor.structured_data_over_budget() {
+                document.warnings.push("stage_budget_exceeded:structured_data".to_string());
+            }
+            if document.read_more_truncated {
+                document.warnings.push("read_more_without_continuation".to_string());
+            }
+        }
+        if self.config.detect_content_language {
+            let largest_block = Self::largest_text_block(&dom, parser);
+            let html_lang = metadata_extractor.get_html_lang();
+            if let Some((language, confidence)) = FastLanguageDetector::detect_content_language(
+                &document.main_content, largest_block.as_deref(), html_lang.as_deref()
+            ) {
+                document.content_language = Some(language);
+                document.content_language_confidence = confidence;
+            }
+        }
+        // Declared <html lang> (primary subtag) wins over detection from the text
+        document.language = metadata_extractor.get_html_lang()
+            .and_then(|lang| lang.split(['-', '_']).next().map(str::to_lowercase))
+            .filter(|lang| !lang.is_empty())
+            .or_else(|| FastLanguageDetector::detect_language(&document.main_content, base_url, DEFAULT_MIN_CONFIDENCE));
+
+        if self.detect_login_gate(&dom, parser, &document.main_content) {
+            document.content_gated = true;
+            document.gate_reason = Some("login".to_string());
+        }
+
+        document.is_placeholder_content = self.is_placeholder_content(&document.title, &document.main_content);
+        document.error_page_reason = self.detect_error_page(&document.title, &document.main_content);
+        document.is_error_page = document.error_page_reason.is_some();
 
         // Extract headings for content structure
         self.extract_headings(&dom, parser, &mut document);
+        document.sections = self.extract_sections(&dom, parser);
+        document.quotes = self.extract_quotes(&dom, parser, base_url);
+        if self.config.definitions {
+            document.definitions = dom.query_selector("dl").into_iter().flatten()
+                .filter_map(|handle| handle.get(parser).and_then(|n| n.as_tag()))
+                .flat_map(|list| main_content_extractor::definition_pairs(list, parser))
+                .collect();
+        }
+        document.lead_paragraph = Self::extract_lead_paragraph(&dom, parser);
+        document.duplicate_metadata = Self::is_duplicate_metadata(&document);
+        if document.duplicate_metadata && self.config.derive_description {
+            let source = document.lead_paragraph.as_deref().unwrap_or(&document.main_content);
+            let derived = self.cleaner.make_preview(source, DERIVED_DESCRIPTION_CHARS);
+            if !derived.is_empty() {
+                document.description = derived;
+            }
+        }
+        if self.config.abbreviations == AbbreviationMode::Synonyms {
+            for node in dom.query_selector("abbr, acronym").into_iter().flatten().filter_map(|h| h.get(parser)) {
+                if let Some((abbr, expansion)) = main_content_extractor::abbreviation_expansion(node, parser) {
+                    document.abbreviations.entry(abbr).or_insert(expansion);
+                }
+            }
+        }
         
         // Create optimized chunks with context
-        document.text_chunks_with_context = self.create_chunks_with_context(&document.main_content, &document.headings);
-        
-        // Calculate essential metrics only
-        self.calculate_essential_metrics(&mut document);
+        if self.config.build_chunks {
+            (document.text_chunks_with_context, document.chunk_diagnostics) =
+                self.create_chunks_with_context(&document.main_content, &document.headings, &document.sections);
+        }
         
+        // Categories and technical scoring share the annotation budget; an overrun drops both
+        let annotation_timer = self.config.stage_timer("annotation");
+        // Stem with the document's own language so non-English text gets sensible stems
+        let stemming_language = self.config.language.clone()
+            .or_else(|| document.content_language.clone())
+            .or_else(|| document.language.clone());
+        document.content_categories = MetadataExtractor::get_content_categories(
+            self.config.analysis_sample(&document.main_content),
+            stemming_language.as_deref(),
+            self.config.min_category_score,
+        );
+        self.calculate_essential_metrics(&mut document, !annotation_timer.exceeded());
+        if annotation_timer.exceeded() {
+            document.content_categories.clear();
+            document.semantic_info.technical_score = 0.0;
+            document.semantic_info.is_technical_content = false;
+            if self.config.collect_warnings {
+                document.warnings.push("stage_budget_exceeded:annotation".to_string());
+            }
+        }
+
         // Detect technical content
         document.is_technical_content = self.is_technical_content(&document);
         if document.is_technical_content {
             document.content_categories.push("technology".into())
         }
         
-        document
+        Ok(document)
     }
 
 
@@ -146,11 +380,12 @@ impl OptimizedExtractor {
             if let Some(heading_nodes) = dom.query_selector(&selector) {
                 for node_handle in heading_nodes {
                     if let Some(node) = node_handle.get(parser) {
-                        let text = node.inner_text(parser).trim().to_string();
-                        if !text.is_empty() && text.len() < 200 {
+                        if let Some(text) = self.clean_heading_text(&node.inner_text(parser)) {
+                            let anchor_id = node.as_tag().and_then(|tag| Self::heading_anchor_id(tag, parser, &text));
                             let heading = Heading {
                                 level: level as u8,
                                 text,
+                                anchor_id,
                             };
                             document.headings.push(heading);
                         }
@@ -160,47 +395,527 @@ impl OptimizedExtractor {
         }
     }
 
-    
-fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec<ChunkWithContext> {
-        if content.is_empty() {
+    /// Every <blockquote> with its cite URL and any <cite> attribution inside it
+    fn extract_quotes(&self, dom: &tl::VDom, parser: &Parser, base_url: &str) -> Vec<Quote> {
+        let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        dom.query_selector("blockquote")
+            .into_iter()
+            .flatten()
+            .filter_map(|handle| handle.get(parser).and_then(|n| n.as_tag()))
+            .filter_map(|tag| {
+                let mut text = String::new();
+                for child in tag.children().top().iter().filter_map(|c| c.get(parser)) {
+                    Self::collect_quote_text(child, parser, &mut text);
+                }
+                let text = normalize(&text);
+                if text.is_empty() {
+                    return None;
+                }
+                let cite_url = tag.attributes().get("cite").flatten()
+                    .map(|c| c.as_utf8_str().trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .map(|c| self.resolve_url(&c, base_url));
+                let source = tag.query_selector(parser, "cite")
+                    .and_then(|mut iter| iter.next())
+                    .and_then(|handle| handle.get(parser))
+                    .map(|node| normalize(&node.inner_text(parser)))
+                    .filter(|s| !s.is_empty());
+                Some(Quote { text, cite_url, source })
+            })
+            .collect()
+    }
+
+    // Quote body text, space-separated per text node; attribution (<cite>, <footer>) is left out
+    fn collect_quote_text(node: &Node, parser: &Parser, out: &mut String) {
+        match node {
+            Node::Tag(tag) => {
+                let name = tag.name().as_utf8_str().to_lowercase();
+                if matches!(name.as_str(), "cite" | "footer" | "script" | "style") {
+                    return;
+                }
+                for child in tag.children().top().iter().filter_map(|c| c.get(parser)) {
+                    Self::collect_quote_text(child, parser, out);
+                }
+            }
+            Node::Raw(text) => {
+                out.push(' ');
+                out.push_str(&text.as_utf8_str());
+            }
+            Node::Comment(_) => {}
+        }
+    }
+
+    /// Synonyms of the keywords (whole keyword or any of its words) from the configured
+    /// map, in keyword order, without duplicates of each other or of the keywords themselves
+    fn expand_keywords(&self, keywords: &[String]) -> Vec<String> {
+        if self.config.keyword_synonyms.is_empty() {
             return Vec::new();
         }
 
+        let mut seen: HashSet<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
+        let mut expansions = Vec::new();
+        for keyword in keywords {
+            let keyword = keyword.to_lowercase();
+            let terms = std::iter::once(keyword.as_str()).chain(keyword.split_whitespace());
+            for synonym in terms.filter_map(|term| self.config.keyword_synonyms.get(term)).flatten() {
+                if expansions.len() >= self.config.max_keyword_expansions {
+                    return expansions;
+                }
+                if seen.insert(synonym.to_lowercase()) {
+                    expansions.push(synonym.clone());
+                }
+            }
+        }
+        expansions
+    }
+
+    /// Standfirst/lede of an article: an explicit lede element, else the first substantial
+    /// <p> of the article or main region. None when neither is clear.
+    fn extract_lead_paragraph(dom: &tl::VDom, parser: &Parser) -> Option<String> {
+        let normalize = |node: &Node| node.inner_text(parser).split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let explicit = LEDE_SELECTORS.iter()
+            .filter_map(|selector| dom.query_selector(selector)?.next()?.get(parser))
+            .map(normalize)
+            .find(|text| text.split_whitespace().count() >= 5);
+        if explicit.is_some() {
+            return explicit;
+        }
+
+        ["article", "main", "[role='main']"].iter()
+            .filter_map(|selector| dom.query_selector(selector)?.next()?.get(parser)?.as_tag())
+            .find_map(|region| {
+                region.query_selector(parser, "p")?
+                    .filter_map(|handle| handle.get(parser))
+                    .map(normalize)
+                    .find(|text| text.split_whitespace().count() >= MIN_LEAD_PARAGRAPH_WORDS)
+            })
+    }
+
+    /// Longest single text block (paragraph, list item, cell, quote), the best
+    /// language sample on pages where the content is a minority of the text
+    fn largest_text_block(dom: &tl::VDom, parser: &Parser) -> Option<String> {
+        dom.query_selector("p, li, td, dd, blockquote, pre")?
+            .filter_map(|handle| handle.get(parser))
+            .map(|node| node.inner_text(parser).split_whitespace().collect::<Vec<_>>().join(" "))
+            .max_by_key(|text| text.chars().count())
+            .filter(|text| !text.is_empty())
+    }
+
+    /// Anchor for deep-linking to a heading: the DOM id on the heading or an
+    /// anchor inside it (`<h2><a id="x">`), falling back to a slug of the text
+    fn heading_anchor_id(tag: &HTMLTag, parser: &Parser, text: &str) -> Option<String> {
+        let attr_value = |tag: &HTMLTag, name: &str| {
+            tag.attributes().get(name).flatten()
+                .map(|v| v.as_utf8_str().trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        if let Some(id) = attr_value(tag, "id") {
+            return Some(id);
+        }
+
+        let inner_anchor = tag.query_selector(parser, "[id], a[name]")
+            .into_iter()
+            .flatten()
+            .filter_map(|handle| handle.get(parser).and_then(|n| n.as_tag()))
+            .find_map(|inner| attr_value(inner, "id").or_else(|| attr_value(inner, "name")));
+        if inner_anchor.is_some() {
+            return inner_anchor;
+        }
+
+        let slug = text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        if slug.is_empty() { None } else { Some(slug) }
+    }
+
+    /// Split the body into document-order sections, one per kept heading.
+    /// Text before the first heading belongs to no section.
+    fn extract_sections(&self, dom: &tl::VDom, parser: &Parser) -> Vec<Section> {
+        let mut sections = Vec::new();
+
+        match dom.query_selector("body").and_then(|mut iter| iter.next()).and_then(|h| h.get(parser)) {
+            Some(body) => self.walk_sections(body, parser, &mut sections),
+            None => {
+                for handle in dom.children() {
+                    if let Some(node) = handle.get(parser) {
+                        self.walk_sections(node, parser, &mut sections);
+                    }
+                }
+            }
+        }
+
+        for section in &mut sections {
+            section.text = section.text.split_whitespace().collect::<Vec<_>>().join(" ");
+            section.reading_ease = FastCleaner::flesch_reading_ease(&section.text);
+        }
+        sections
+    }
+
+    fn walk_sections(&self, node: &Node, parser: &Parser, sections: &mut Vec<Section>) {
+        match node {
+            Node::Tag(tag) => {
+                let tag_name = tag.name().as_utf8_str().to_lowercase();
+                if matches!(tag_name.as_str(), "script" | "style" | "noscript" | "nav" | "header" | "footer" | "aside" | "form") {
+                    return;
+                }
+
+                let heading_level = match tag_name.as_str() {
+                    "h1" => Some(1), "h2" => Some(2), "h3" => Some(3),
+                    "h4" => Some(4), "h5" => Some(5), "h6" => Some(6),
+                    _ => None,
+                };
+                if let Some(level) = heading_level {
+                    if let Some(heading) = self.clean_heading_text(&tag.inner_text(parser)) {
+                        sections.push(Section {
+                            anchor_id: Self::heading_anchor_id(tag, parser, &heading),
+                            heading,
+                            level,
+                            text: String::new(),
+                            reading_ease: None,
+                        });
+                    }
+                    return;
+                }
+
+                for child in tag.children().top().iter() {
+                    if let Some(child_node) = child.get(parser) {
+                        self.walk_sections(child_node, parser, sections);
+                    }
+                }
+            }
+            Node::Raw(text) => {
+                if let Some(current) = sections.last_mut() {
+                    current.text.push(' ');
+                    current.text.push_str(&text.as_utf8_str());
+                }
+            }
+            Node::Comment(_) => {}
+        }
+    }
+
+    /// Anchor of the section a chunk starts in, found by locating the chunk's
+    /// opening words in each section's cleaned text
+    fn find_section(chunk_text: &str, section_texts: &[String]) -> Option<usize> {
+        let words: Vec<&str> = chunk_text.split_whitespace().collect();
+
+        // Shorter probes tolerate text the main-content walk dropped mid-sentence
+        for probe_len in [6, 3] {
+            if words.len() < probe_len {
+                continue;
+            }
+            let probe = words[..probe_len].join(" ");
+            if let Some(index) = section_texts.iter().position(|text| text.contains(&probe)) {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// Headings from the outermost enclosing section down to `sections[index]`
+    fn section_path(sections: &[Section], index: usize) -> Vec<String> {
+        let mut path = vec![sections[index].heading.clone()];
+        let mut level = sections[index].level;
+        for section in sections[..index].iter().rev() {
+            if section.level < level {
+                path.push(section.heading.clone());
+                level = section.level;
+            }
+        }
+        path.reverse();
+        path
+    }
+
+    /// Distinct http(s) links of the page in document order, resolved against `base_url`
+    /// and capped at MAX_LINKS. Fragment-only, javascript:, mailto: and tel: hrefs are skipped.
+    fn extract_links(&self, dom: &tl::VDom, parser: &Parser, base_url: &str) -> Vec<LinkInfo> {
+        let bare_host = |url: &str| url::Url::parse(url).ok()
+            .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_lowercase()));
+        let page_host = bare_host(base_url);
+
+        let mut seen = HashSet::new();
+        let mut links = Vec::new();
+        for tag in dom.query_selector("a[href]").into_iter().flatten()
+            .filter_map(|handle| handle.get(parser).and_then(|n| n.as_tag()))
+        {
+            let attrs = tag.attributes();
+            let Some(raw) = attrs.get("href").flatten().map(|h| h.as_utf8_str().trim().to_string()) else { continue };
+            let lower = raw.to_lowercase();
+            if raw.is_empty() || raw.starts_with('#')
+                || ["javascript:", "mailto:", "tel:", "data:"].iter().any(|scheme| lower.starts_with(scheme)) {
+                continue;
+            }
+            let href = self.resolve_url(&raw, base_url);
+            if !href.starts_with("http") || !seen.insert(href.clone()) {
+                continue;
+            }
+            let text = tag.inner_text(parser).split_whitespace().collect::<Vec<_>>().join(" ");
+            links.push(LinkInfo {
+                anchor_quality: Self::anchor_text_quality(&text, &href),
+                is_external: bare_host(&href) != page_host,
+                rel: attrs.get("rel").flatten().map(|r| r.as_utf8_str().trim().to_lowercase()).filter(|r| !r.is_empty()),
+                text,
+                href,
+            });
+            if links.len() >= MAX_LINKS {
+                break;
+            }
+        }
+        links
+    }
+
+    /// Detect pages whose content is replaced by a login/registration wall.
+    /// A gate needs a thin body plus either gate wording or a password form;
+    /// a "Log in" link on an otherwise full article is not enough.
+    fn detect_login_gate(&self, dom: &tl::VDom, parser: &Parser, main_content: &str) -> bool {
+        let word_count = main_content.split_whitespace().count();
+        let content_lower = main_content.to_lowercase();
+
+        let has_gate_phrase = LOGIN_GATE_PHRASES.iter().any(|phrase| content_lower.contains(phrase));
+        if has_gate_phrase && word_count < 300 {
+            return true;
+        }
+
+        // An auth form dominating a thin page is a gate even without the wording
+        let has_password_form = dom.query_selector("input[type='password']")
+            .map(|mut nodes| nodes.any(|node| node.get(parser).is_some()))
+            .unwrap_or(false);
+        has_password_form && word_count < 150
+    }
+
+    /// Detect soft 404s (error pages served with HTTP 200). Only thin pages are flagged, so
+    /// articles that discuss 404 errors at length are left alone. Returns the reason:
+    /// "error_title" (status code or error phrase in the title) or "error_message" (body phrase).
+    fn detect_error_page(&self, title: &str, content: &str) -> Option<String> {
+        let word_count = content.split_whitespace().count();
+        let title_lower = title.to_lowercase();
+        let content_lower = content.to_lowercase();
+        let has_pattern = |text: &str| {
+            self.config.error_page_patterns.iter().any(|p| !p.is_empty() && text.contains(p.as_str()))
+        };
+
+        // A title that *is* the status ("404", "Error 404 - Example"), not one that mentions it
+        let title_is_status = ERROR_TITLE.is_match(&title_lower);
+        if (title_is_status || has_pattern(&title_lower)) && word_count < 150 {
+            return Some("error_title".to_string());
+        }
+        if has_pattern(&content_lower) && word_count < 100 {
+            return Some("error_message".to_string());
+        }
+        None
+    }
+
+    /// Title, first H1 and description all say the same thing (a thin-page signal). The
+    /// title may carry a trailing " | Site" / " - Site" segment the others lack.
+    fn is_duplicate_metadata(document: &ProcessedDocument) -> bool {
+        let normalize = |text: &str| {
+            text.split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(|w| w.to_lowercase())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let Some(h1) = document.headings.iter().find(|h| h.level == 1).map(|h| normalize(&h.text)) else {
+            return false;
+        };
+        let title = normalize(&document.title);
+        let title_matches = title == h1 || document.title
+            .rsplit_once(['|', '-', '–', '—', '·'])
+            .map(|(head, _)| normalize(head) == h1)
+            .unwrap_or(false);
+        !h1.is_empty() && title_matches && normalize(&document.description) == h1
+    }
+
+    /// Detect unfilled CMS templates: lorem-ipsum-dominated text, or configured
+    /// template markers that make up the page rather than a passing mention.
+    fn is_placeholder_content(&self, title: &str, content: &str) -> bool {
+        let content_lower = content.to_lowercase();
+        let words: Vec<&str> = content_lower
+            .split_whitespace()
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        if words.len() >= 10 {
+            let filler = words.iter().filter(|w| LOREM_IPSUM_WORDS.contains(w)).count();
+            if filler as f32 / words.len() as f32 > 0.4 {
+                return true;
+            }
+        }
+
+        let title_lower = title.to_lowercase();
+        let marker_hits: usize = self.config.placeholder_patterns.iter()
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| content_lower.matches(pattern.as_str()).count() + title_lower.matches(pattern.as_str()).count())
+            .sum();
+
+        marker_hits >= 2 || (marker_hits == 1 && words.len() < 50)
+    }
+
+    /// Score how descriptive a link's anchor text is, from 0.0 (junk) to 1.0.
+    /// Empty, generic ("click here") and URL-like anchors score low; descriptive
+    /// multi-word anchors score high.
+    pub fn anchor_text_quality(text: &str, href: &str) -> f32 {
+        let text = text.trim();
+        if text.is_empty() {
+            return 0.0;
+        }
+
+        let text_lower = text.to_lowercase();
+        let normalized = text_lower.trim_matches(|c: char| !c.is_alphanumeric());
+
+        if LINK_INDICATORS.contains(&normalized) || GENERIC_ANCHOR_WORDS.contains(&normalized) {
+            return 0.05;
+        }
+
+        if normalized.starts_with("http") || normalized.starts_with("www.")
+            || text_lower.contains("://") || text == href.trim() {
+            return 0.1;
+        }
+
+        let word_count = text.split_whitespace().count();
+        let mut score: f32 = match word_count {
+            1 => 0.4,
+            2 => 0.6,
+            3..=8 => 0.9,
+            _ => 0.7, // Whole sentences as anchors are less focused
+        };
+
+        // "click here for the guide" still leans on a generic phrase
+        if LINK_INDICATORS.iter().any(|&indicator| text_lower.contains(indicator)) {
+            score *= 0.6;
+        }
+
+        // Mostly digits/symbols (IDs, dates, "[1]") describe nothing
+        let alpha_chars = text.chars().filter(|c| c.is_alphabetic()).count();
+        if alpha_chars * 2 < text.chars().count() {
+            score *= 0.5;
+        }
+
+        score.clamp(0.0, 1.0)
+    }
+
+    /// Trim navigation glyphs and drop junk headings ("»", "1", "•").
+    /// Short but meaningful headings such as "FAQ" are kept.
+    fn clean_heading_text(&self, raw: &str) -> Option<String> {
+        let text = raw.trim_matches(|c: char| c.is_whitespace() || HEADING_GLYPHS.contains(&c));
+        if text.is_empty() || text.len() >= 200 {
+            return None;
+        }
+
+        // Purely numeric or symbolic headings carry no meaning
+        if !text.chars().any(|c| c.is_alphabetic()) {
+            return None;
+        }
+
+        let meaningful_chars = text.chars().filter(|c| c.is_alphanumeric()).count();
+        if meaningful_chars < self.config.min_heading_length {
+            return None;
+        }
+
+        Some(self.cleaner.normalize_heading_case(text, self.config.heading_case))
+    }
+
+    /// Chunks that pass the quality gates, plus a diagnostic for each dropped chunk
+    /// when `chunk_diagnostics` is enabled
+    fn create_chunks_with_context(&self, content: &str, headings: &[Heading], sections: &[Section]) -> (Vec<ChunkWithContext>, Vec<ChunkDiagnostic>) {
+        if content.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+
         // 🧹 Use FastCleaner for proper chunking with comprehensive cleaning
-        let cleaner = FastCleaner::new();
+        let cleaner = &self.cleaner;
         
         // First, clean the content thoroughly to remove HTML entities and noise
         let cleaned_content = cleaner.clean_text(content);
         
-        // Use FastCleaner's optimized chunking method (with less restrictive size requirements)
-        let raw_chunks = cleaner.create_chunks(&cleaned_content, 2500, 50);  // Reduced from 100 to 50
+        // Chunk budget (max/min size, overlap) comes from the processing options
+        let raw_chunks = cleaner.create_chunks(&cleaned_content);
+
+        // Section text goes through the same cleaning so chunk openings can be located in it
+        let section_texts: Vec<String> = sections.iter()
+            .map(|section| cleaner.clean_text(&format!("{} {}", section.heading, section.text)))
+            .collect();
         
+        let build_chunk = |index: usize, chunk_text: String, low_confidence: bool| {
+            let section = Self::find_section(&chunk_text, &section_texts);
+            ChunkWithContext {
+                relevant_headings: self.find_relevant_headings_for_chunk(&chunk_text, headings),
+                section_anchor: section.and_then(|i| sections[i].anchor_id.clone()),
+                section_path: section.map(|i| Self::section_path(sections, i)).unwrap_or_default(),
+                contextualized_text: None,
+                language: FastLanguageDetector::detect_chunk_language(&chunk_text),
+                text_chunk: chunk_text,
+                chunk_index: index,
+                low_confidence,
+            }
+        };
+
+        // Filtering is independent per chunk; large documents assess chunks in parallel
+        // (collect keeps the original order, so indices and output match the sequential path)
+        let assess = |(index, chunk_text): (usize, String)| {
+            // Additional filtering for web-specific noise that might slip through
+            let rejection = self.chunk_rejection_reason(&chunk_text)
+                .or_else(|| self.web_noise_reason(&chunk_text));
+            match rejection {
+                Some(reason) => Err((index, chunk_text, reason)),
+                None => Ok(build_chunk(index, chunk_text, false)),
+            }
+        };
+        let assessed: Vec<_> = if raw_chunks.len() > self.config.parallel_chunk_threshold {
+            raw_chunks.into_par_iter().enumerate().map(assess).collect()
+        } else {
+            raw_chunks.into_iter().enumerate().map(assess).collect()
+        };
+
         let mut chunks_with_context = Vec::new();
+        let mut rejected = Vec::new();
+        let mut diagnostics = Vec::new();
         
-        for (index, chunk_text) in raw_chunks.into_iter().enumerate() {
-            // Additional filtering for web-specific noise that might slip through
-            if self.is_chunk_meaningful(&chunk_text) && !self.contains_web_noise(&chunk_text) {
-                let relevant_headings = self.find_relevant_headings_for_chunk(&chunk_text, headings);
-                
-                chunks_with_context.push(ChunkWithContext {
-                    text_chunk: chunk_text,
-                    relevant_headings,
-                    chunk_index: index,
-                });
+        for outcome in assessed {
+            match outcome {
+                Ok(chunk) => chunks_with_context.push(chunk),
+                Err((index, chunk_text, reason)) => {
+                    if self.config.chunk_diagnostics {
+                        diagnostics.push(ChunkDiagnostic {
+                            chunk_index: index,
+                            reason: reason.to_string(),
+                            preview: chunk_text.chars().take(80).collect(),
+                        });
+                    }
+                    if self.config.guarantee_chunks {
+                        rejected.push((index, chunk_text));
+                    }
+                }
             }
         }
 
-        chunks_with_context
+        // Listing/reference pages can lose every chunk to the filters; keep them
+        // searchable with the unfiltered chunks, flagged as low confidence
+        if chunks_with_context.is_empty() {
+            chunks_with_context = rejected.into_iter()
+                .filter(|(_, chunk_text)| !chunk_text.trim().is_empty())
+                .map(|(index, chunk_text)| build_chunk(index, chunk_text, true))
+                .collect();
+        }
+
+        (chunks_with_context, diagnostics)
     }
     
-    fn contains_web_noise(&self, text: &str) -> bool {
+    /// Why `text` looks like web noise (CSS, wiki chrome, JSON, navigation), or None if clean
+    fn web_noise_reason(&self, text: &str) -> Option<&'static str> {
+        let thresholds = &self.config.chunk_filter;
         let text_lower = text.to_lowercase();
         
         // Check for HTML-encoded entities that might slip through
         if text.contains("\\u003c") || text.contains("\\u003e") || 
            text.contains("\\u0026") || text.contains("&nbsp;") ||
            text.contains("&amp;") || text.contains("&lt;") || text.contains("&gt;") {
-            return true;
+            return Some("encoded_entities");
         }
         
         // CRITICAL: Check for CSS patterns that indicate stylesheet content
@@ -216,7 +931,7 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
            text.contains("border-top:") || text.contains("float:") ||
            text.contains("max-width:") || text.contains("@media") ||
            text.contains("counter-reset:") || text.contains("counter-increment:") {
-            return true;
+            return Some("css_pattern");
         }
         
         // Check for MediaWiki-specific patterns
@@ -224,14 +939,14 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
            text.contains("Retrieved from") || text.contains("Hidden categories:") ||
            text.contains("Articles with") || text.contains("Pages with") ||
            text.contains("Webarchive template") || text.contains("Commons category") {
-            return true;
+            return Some("mediawiki_noise");
         }
         
         // Check for JSON remnants
         if text.contains("\"type\":") || text.contains("\"href\":") || 
            text.contains("\"title\":") || text.contains("\"class\":") ||
            text.contains("\"id\":") || text.contains("\"style\":") {
-            return true;
+            return Some("json_remnants");
         }
         
         // Enhanced interface/navigation noise detection
@@ -250,25 +965,25 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
             .map(|&noise| text_lower.matches(noise).count())
             .sum::<usize>();
         
-        // If more than 20% of the text is interface noise, reject it
+        // Reject when too much of the text is interface noise
         let word_count = text.split_whitespace().count();
-        if word_count > 0 && (noise_count as f32 / word_count as f32) > 0.2 {
-            return true;
+        if word_count > 0 && (noise_count as f32 / word_count as f32) > thresholds.max_interface_noise_ratio {
+            return Some("interface_noise");
         }
         
         // Check for excessive CSS-like patterns (lots of colons and semicolons)
         let css_chars = text.chars().filter(|c| *c == ':' || *c == ';').count();
         if css_chars > 20 && text.len() > 500 {
             let css_density = css_chars as f32 / text.len() as f32;
-            if css_density > 0.01 { // More than 1% CSS characters
-                return true;
+            if css_density > thresholds.max_css_density {
+                return Some("css_density");
             }
         }
         
         // Check for excessive version numbers and technical IDs (like [1.0], [2.1], etc.)
         let version_pattern_count = text.matches(|c: char| c == '[' || c == ']').count();
-        if version_pattern_count > 10 {
-            return true;
+        if version_pattern_count > thresholds.max_brackets {
+            return Some("bracket_density");
         }
         
         // Check for excessive technical abbreviations and acronyms
@@ -278,52 +993,52 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
             .filter(|window| window.iter().all(|c| c.is_uppercase() || !c.is_alphabetic()))
             .count();
         
-        if uppercase_sequences > word_count / 4 {
-            return true;
+        if uppercase_sequences as f32 > word_count as f32 * thresholds.max_uppercase_ratio {
+            return Some("uppercase_density");
         }
         
         // Check for excessive navigation/link text
-        let link_indicators = ["click here", "read more", "learn more", "view all", 
-                              "home page", "contact us", "about us", "privacy policy"];
-        if link_indicators.iter().any(|&indicator| text_lower.contains(indicator)) {
+        if LINK_INDICATORS.iter().any(|&indicator| text_lower.contains(indicator)) {
             // Only reject if it's mostly navigation content
             let total_words = text.split_whitespace().count();
-            let nav_words = link_indicators.iter()
+            let nav_words = LINK_INDICATORS.iter()
                 .map(|&indicator| text_lower.matches(indicator).count() * indicator.split_whitespace().count())
                 .sum::<usize>();
             
-            if total_words > 0 && (nav_words as f32 / total_words as f32) > 0.3 {
-                return true;
+            if total_words > 0 && (nav_words as f32 / total_words as f32) > thresholds.max_navigation_ratio {
+                return Some("navigation_links");
             }
         }
         
-        false
+        None
     }
     
-    fn is_chunk_meaningful(&self, chunk: &str) -> bool {
+    /// Why `chunk` carries too little readable content to index, or None if it passes
+    fn chunk_rejection_reason(&self, chunk: &str) -> Option<&'static str> {
+        let thresholds = &self.config.chunk_filter;
         let chunk = chunk.trim();
         
-        // Must have minimum length (reduced from 30 to 20)
-        if chunk.len() < 20 {
-            return false;
+        // Must have minimum length
+        if chunk.len() < thresholds.min_chars {
+            return Some("too_short");
         }
         
-        // Check for reasonable sentence structure (reduced from 5 to 3 words)
+        // Check for reasonable sentence structure
         let words: Vec<&str> = chunk.split_whitespace().collect();
-        if words.len() < 3 {
-            return false;
+        if words.len() < thresholds.min_words {
+            return Some("too_few_words");
         }
         
-        // Must contain some alphabetic content (made more lenient)
+        // Must contain some alphabetic content
         let alpha_chars = chunk.chars().filter(|c| c.is_alphabetic()).count();
-        if alpha_chars < chunk.len() / 5 { // Reduced from 1/4 to 1/5
-            return false;
+        if (alpha_chars as f32) < chunk.len() as f32 * thresholds.min_alpha_ratio {
+            return Some("low_alpha");
         }
         
-        // Check for too much JSON-like content (made more lenient)
+        // Check for too much JSON-like content
         let json_chars = chunk.chars().filter(|c| "{}[]\",:;".contains(*c)).count();
-        if json_chars > chunk.len() / 3 { // Increased from 1/4 to 1/3
-            return false;
+        if json_chars as f32 > chunk.len() as f32 * thresholds.max_json_ratio {
+            return Some("json_density");
         }
         
         // Must contain some readable English words (made more lenient)
@@ -332,8 +1047,8 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
             chunk.to_lowercase().contains(word)
         }).count();
         
-        // Need at least 1 common word (was requiring any, now more explicit)
-        word_count >= 1
+        // Need at least 1 common word
+        if word_count >= 1 { None } else { Some("no_common_words") }
     }
 
     fn find_relevant_headings_for_chunk(&self, chunk_text: &str, headings: &[Heading]) -> Vec<String> {
@@ -368,6 +1083,31 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
     }
 
 
+    // Normalized date, recording "date_unparseable:<raw>" when warnings are collected
+    fn normalize_date(&self, raw: String, warnings: &mut Vec<String>) -> Option<String> {
+        let normalized = self.cleaner.normalize_date(&raw);
+        if self.config.collect_warnings && normalized.is_none() && !raw.trim().is_empty() {
+            warnings.push(format!("date_unparseable:{}", raw.trim()));
+        }
+        normalized
+    }
+
+    fn normalize_durations(&self, raw_durations: Vec<(String, String)>) -> Vec<DurationInfo> {
+        let cleaner = &self.cleaner;
+        raw_durations
+            .into_iter()
+            .filter_map(|(field, raw)| {
+                let seconds = cleaner.parse_iso_duration(&raw)?;
+                Some(DurationInfo {
+                    field,
+                    human: cleaner.format_duration(seconds),
+                    raw,
+                    seconds,
+                })
+            })
+            .collect()
+    }
+
     fn resolve_url(&self, url: &str, base_url: &str) -> String {
         if url.starts_with("http") {
             url.to_string()
@@ -457,7 +1197,8 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
     }
 
  
-    fn calculate_essential_metrics(&self, document: &mut ProcessedDocument) {
+    // `annotate` false leaves the technical score at zero (annotation stage over budget)
+    fn calculate_essential_metrics(&self, document: &mut ProcessedDocument, annotate: bool) {
         let words: Vec<&str> = document.main_content
             .split_whitespace()
             .filter(|w| w.len() > 2)
@@ -465,21 +1206,29 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
         
         document.word_count = words.len();
         
+        let (max_heading_depth, heading_levels_used) = Self::heading_structure(&document.sections);
+
+        let (reading_ease, grade_level) = FastCleaner::readability(&document.main_content).unwrap_or((0.0, 0.0));
         // Calculate semantic info with essential fields only
         document.semantic_info = SemanticInfo {
             word_count: document.word_count,
+            content_word_count: FastCleaner::count_content_words(&document.main_content),
             sentence_count: document.main_content.matches('.').count(),
             paragraph_count: document.main_content.matches('\n').count().max(1),
             reading_time_minutes: (document.word_count as f32 / 200.0).max(1.0),
             content_quality_score: self.calculate_quality_score(&document.main_content, &document.headings),
-            is_technical_content: self.calculate_technical_score(&document.main_content) > 0.3,
+            is_technical_content: annotate && self.calculate_technical_score(&document.main_content) > 0.3,
             headings_count: document.headings.len(),
+            max_heading_depth,
+            heading_levels_used,
             images_count: if document.primary_image.is_some() { 1 } else { 0 },
-            links_count: 0, // We don't extract links in optimized version
-            technical_score: self.calculate_technical_score(&document.main_content),
+            links_count: document.links.len(),
+            technical_score: if annotate { self.calculate_technical_score(&document.main_content) } else { 0.0 },
             avg_sentence_length: if document.semantic_info.sentence_count > 0 {
                 document.word_count as f32 / document.semantic_info.sentence_count as f32
             } else { 0.0 },
+            flesch_reading_ease: reading_ease as f32,
+            flesch_kincaid_grade: grade_level as f32,
             content_density: document.word_count as f32 / document.main_content.len().max(1) as f32,
             domain_score: 0.0, // Default or update as needed
         };
@@ -487,6 +1236,28 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
         document.content_quality_score = document.semantic_info.content_quality_score;
     }
 
+    /// (max nesting depth, distinct levels) of the document-order heading outline. A heading
+    /// nests under the nearest preceding heading of a higher level, so h1 h2 h3 is depth 3
+    /// while ten h2s are depth 1.
+    fn heading_structure(sections: &[Section]) -> (usize, usize) {
+        let mut open_levels: Vec<u8> = Vec::new();
+        let mut max_depth = 0;
+        let mut levels_used = HashSet::new();
+
+        for section in sections {
+            while open_levels.last().is_some_and(|&open| open >= section.level) {
+                open_levels.pop();
+            }
+            open_levels.push(section.level);
+            max_depth = max_depth.max(open_levels.len());
+            levels_used.insert(section.level);
+        }
+
+        (max_depth, levels_used.len())
+    }
+
+    // Provisional 0-1 quality from length and structure alone; process_html replaces it
+    // with the ContentScorer result on the same scale
     fn calculate_quality_score(&self, content: &str, headings: &[Heading]) -> f32 {
         let mut score = 0.0;
         
@@ -505,10 +1276,11 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
             score += 0.5;
         }
         
-        score.min(5.0)
+        (score / 5.0).min(1.0)
     }
 
     fn calculate_technical_score(&self, content: &str) -> f32 {
+        let content = self.config.analysis_sample(content);
         let technical_count = self.tech_pattern.find_iter(content).count();
         (technical_count as f32 / content.len().max(1) as f32) * 1000.0
     }
@@ -522,3 +1294,52 @@ fn create_chunks_with_context(&self, content: &str, headings: &[Heading]) -> Vec
 
     
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broken_markup_does_not_panic() {
+        let invalid_bytes = String::from_utf8_lossy(b"<p>caf\xe9 \xff\xfe</p>").into_owned();
+        let inputs = [
+            "<html><body><div><p>Unterminated <b>bold <i>text",
+            "<<<>>><div class=\"a><p>Broken attribute</div></span></html>",
+            "<script>var x = '</div>';<!-- unterminated comment <p>text",
+            "</p></div></body></html><html>",
+            invalid_bytes.as_str(),
+        ];
+        let extractor = OptimizedExtractor::with_config(ProcessingConfig::default());
+        for html in inputs {
+            let _ = extractor.extract_content(html, "https://example.com/page");
+        }
+    }
+
+    #[test]
+    fn published_dates_are_normalized_to_iso_8601() {
+        let extractor = OptimizedExtractor::with_config(ProcessingConfig::default());
+        let cases = [
+            ("August 25, 2025", "2025-08-25T00:00:00Z"),
+            ("25.08.2025", "2025-08-25T00:00:00Z"),
+            ("7/29/2025, 9:28:40 AM", "2025-07-29T09:28:40Z"),
+        ];
+        for (raw, expected) in cases {
+            let html = format!(
+                "<html><head><meta property=\"article:published_time\" content=\"{}\"></head>\
+                 <body><p>Body text</p></body></html>",
+                raw
+            );
+            let document = extractor.extract_content(&html, "https://example.com/post").unwrap();
+            assert_eq!(document.published_date.as_deref(), Some(expected), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn junk_headings_are_dropped_and_short_meaningful_ones_kept() {
+        let extractor = OptimizedExtractor::with_config(ProcessingConfig::default());
+        assert_eq!(extractor.clean_heading_text("»"), None);
+        assert_eq!(extractor.clean_heading_text("1"), None);
+        assert_eq!(extractor.clean_heading_text("FAQ").as_deref(), Some("FAQ"));
+        assert_eq!(extractor.clean_heading_text("» Installation ¶").as_deref(), Some("Installation"));
+    }
+}
@@ -0,0 +1,194 @@
+use regex::Regex;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use url::Url;
+use crate::cleaner::FastCleaner;
+use crate::types::{FeedDocument, FeedItem};
+
+// tl treats <link> as a void element and mangles CDATA, so feeds are scanned with
+// a small set of tolerant patterns instead of the HTML parser.
+static RSS_ITEM: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<item\b[^>]*>(.*?)</item>").unwrap());
+static ATOM_ENTRY: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<entry\b[^>]*>(.*?)</entry>").unwrap());
+static ATOM_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<link\b([^>]*?)/?>").unwrap());
+static ATTRIBUTE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)([a-z_:][-a-z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+});
+static CDATA: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<!\[CDATA\[(.*?)\]\]>").unwrap());
+static MARKUP_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+static HTTP_EQUIV_CONTENT_TYPE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<meta\b[^>]*http-equiv\s*=\s*["']?content-type["']?[^>]*content\s*=\s*["']([^"']*)["']"#).unwrap()
+});
+static WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+// Elements read from channels, items and entries, each with its pattern compiled once
+const FEED_ELEMENTS: &[&str] = &[
+    "title", "link", "guid", "pubDate", "dc:date", "description", "content:encoded", "author",
+    "dc:creator", "published", "updated", "summary", "content", "name", "email",
+];
+static ELEMENT_PATTERNS: Lazy<HashMap<&'static str, Regex>> = Lazy::new(|| {
+    FEED_ELEMENTS.iter()
+        .map(|name| (*name, Regex::new(&format!(r"(?is)<{0}\b[^>]*>(.*?)</{0}>", regex::escape(name))).unwrap()))
+        .collect()
+});
+// RSS <author> is usually "editor@example.com (Jane Doe)"
+static RSS_AUTHOR_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\S+@\S+\s*\((.+)\)$").unwrap());
+
+/// True when the payload is an RSS/Atom/RDF feed rather than an HTML page:
+/// either the root element is a feed element or a <meta http-equiv> declares a feed type
+pub fn is_feed_payload(content: &str) -> bool {
+    if let Some(root) = root_element_name(content) {
+        if matches!(root.as_str(), "rss" | "feed" | "rdf:rdf") {
+            return true;
+        }
+    }
+
+    // Only the head matters for http-equiv; avoid scanning large bodies
+    let head_end = content.char_indices().nth(4096).map(|(i, _)| i).unwrap_or(content.len());
+    HTTP_EQUIV_CONTENT_TYPE.captures(&content[..head_end])
+        .and_then(|caps| caps.get(1))
+        .map(|m| {
+            let declared = m.as_str().to_lowercase();
+            declared.contains("rss+xml") || declared.contains("atom+xml") || declared.contains("rdf+xml")
+        })
+        .unwrap_or(false)
+}
+
+/// Name of the first element, skipping the XML declaration, comments,
+/// processing instructions and doctype
+fn root_element_name(content: &str) -> Option<String> {
+    let mut rest = content.trim_start_matches('\u{feff}').trim_start();
+
+    loop {
+        if let Some(after) = rest.strip_prefix("<?") {
+            rest = after.split_once("?>")?.1.trim_start();
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.split_once("-->")?.1.trim_start();
+        } else if rest.starts_with("<!") {
+            rest = rest.split_once('>')?.1.trim_start();
+        } else {
+            break;
+        }
+    }
+
+    let name: String = rest.strip_prefix('<')?
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == ':' || *c == '-' || *c == '_')
+        .collect();
+    if name.is_empty() { None } else { Some(name.to_lowercase()) }
+}
+
+/// Parse an RSS 2.0 / RDF or Atom feed into its title, link and items.
+/// Relative links are resolved against `base_url` and dates normalized to UTC ISO-8601.
+pub fn parse_feed(xml: &str, base_url: &str) -> FeedDocument {
+    let is_atom = root_element_name(xml).as_deref() == Some("feed");
+    let base = Url::parse(base_url).ok();
+    let cleaner = FastCleaner::new();
+    let item_pattern: &Regex = if is_atom { &ATOM_ENTRY } else { &RSS_ITEM };
+
+    let items: Vec<FeedItem> = item_pattern
+        .captures_iter(xml)
+        .filter_map(|caps| caps.get(1))
+        .map(|block| {
+            let block = block.as_str();
+            let mut item = if is_atom { parse_atom_entry(block) } else { parse_rss_item(block) };
+            item.link = item.link.map(|link| resolve_link(base.as_ref(), link));
+            item.published = item.published.and_then(|date| cleaner.normalize_date(&date));
+            item
+        })
+        .collect();
+
+    // Channel-level fields live outside the item blocks
+    let channel = item_pattern.replace_all(xml, "");
+    let link = if is_atom { atom_link(&channel) } else { element_text(&channel, &["link"]) };
+
+    FeedDocument {
+        title: element_text(&channel, &["title"]).unwrap_or_default(),
+        link: link.map(|link| resolve_link(base.as_ref(), link)),
+        items,
+    }
+}
+
+fn resolve_link(base: Option<&Url>, link: String) -> String {
+    match base {
+        Some(base) => base.join(&link).map(|u| u.to_string()).unwrap_or(link),
+        None => link,
+    }
+}
+
+fn parse_rss_item(block: &str) -> FeedItem {
+    FeedItem {
+        title: element_text(block, &["title"]).unwrap_or_default(),
+        link: element_text(block, &["link", "guid"]),
+        published: element_text(block, &["pubDate", "dc:date"]),
+        summary: element_text(block, &["description", "content:encoded"]),
+        author: element_text(block, &["author", "dc:creator"]).map(|author| {
+            match RSS_AUTHOR_NAME.captures(&author) {
+                Some(caps) => caps[1].trim().to_string(),
+                None => author,
+            }
+        }),
+    }
+}
+
+fn parse_atom_entry(block: &str) -> FeedItem {
+    FeedItem {
+        title: element_text(block, &["title"]).unwrap_or_default(),
+        link: atom_link(block),
+        published: element_text(block, &["published", "updated"]),
+        summary: element_text(block, &["summary", "content"]),
+        author: element_inner(block, "author")
+            .and_then(|author| element_text(author, &["name"]).or_else(|| element_text(author, &["email"])))
+            .or_else(|| element_text(block, &["dc:creator"])),
+    }
+}
+
+/// Raw inner markup of the first `name` element; `name` must be one of FEED_ELEMENTS
+fn element_inner<'a>(block: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = ELEMENT_PATTERNS.get(name);
+    debug_assert!(pattern.is_some(), "{} is not in FEED_ELEMENTS", name);
+    pattern?.captures(block)?.get(1).map(|m| m.as_str())
+}
+
+/// Text of the first present element among `names`, with CDATA unwrapped,
+/// markup stripped and entities decoded
+fn element_text(block: &str, names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| {
+        let text = clean_feed_text(element_inner(block, name)?);
+        if text.is_empty() { None } else { Some(text) }
+    })
+}
+
+/// Atom links are empty elements: prefer rel="alternate" (or no rel), then any href
+fn atom_link(block: &str) -> Option<String> {
+    let mut fallback = None;
+
+    for caps in ATOM_LINK.captures_iter(block) {
+        let attrs = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+        let mut href = None;
+        let mut rel = None;
+        for attr in ATTRIBUTE.captures_iter(attrs) {
+            let value = attr.get(2).or_else(|| attr.get(3)).map(|m| m.as_str().trim().to_string());
+            match attr[1].to_lowercase().as_str() {
+                "href" => href = value,
+                "rel" => rel = value,
+                _ => {}
+            }
+        }
+
+        let Some(href) = href.filter(|h| !h.is_empty()) else { continue };
+        match rel.as_deref() {
+            None | Some("alternate") => return Some(href),
+            _ => { fallback.get_or_insert(href); }
+        }
+    }
+
+    fallback
+}
+
+fn clean_feed_text(raw: &str) -> String {
+    let unwrapped = CDATA.replace_all(raw, "$1");
+    // Escaped HTML in descriptions decodes to markup (and its own entities),
+    // so strip and decode a second time
+    let decoded = FastCleaner::decode_entities(&MARKUP_TAG.replace_all(&unwrapped, " "));
+    let stripped = FastCleaner::decode_entities(&MARKUP_TAG.replace_all(&decoded, " "));
+    WHITESPACE.replace_all(&stripped, " ").trim().to_string()
+}
@@ -1,4 +1,4 @@
-use whatlang::{detect, Lang};
+use whatlang::{detect, Detector, Lang};
 use url::Url;
 use std::collections::HashSet;
 use once_cell::sync::Lazy;
@@ -25,11 +25,59 @@ static ENGLISH_DOMAIN_NAMES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     ].into_iter().collect()
 });
 
+// Country-code TLDs that are used as generic domains and say nothing about the audience
+const GENERIC_CCTLDS: &[&str] = &["io", "co", "tv", "me", "ai", "ly", "fm", "gg", "cc", "ws", "to", "is", "so"];
+
+// Main language of a ccTLD's audience, used only when the page declares no language
+const CCTLD_LANGUAGES: &[(&str, &str)] = &[
+    ("uk", "en"), ("us", "en"), ("au", "en"), ("nz", "en"), ("ie", "en"),
+    ("de", "de"), ("at", "de"), ("fr", "fr"), ("es", "es"), ("mx", "es"), ("ar", "es"),
+    ("it", "it"), ("pt", "pt"), ("br", "pt"), ("nl", "nl"), ("pl", "pl"), ("ru", "ru"),
+    ("jp", "ja"), ("kr", "ko"), ("cn", "zh"), ("tw", "zh"), ("se", "sv"), ("dk", "da"),
+    ("no", "no"), ("fi", "fi"), ("tr", "tr"), ("gr", "el"), ("cz", "cs"),
+];
+
+// Signal weights for resolve_locale; a resolved locale's confidence is the summed
+// weight of the signals that agree with it
+const HTML_LANG_WEIGHT: f32 = 0.4;
+const OG_LOCALE_WEIGHT: f32 = 0.3;
+const HREFLANG_WEIGHT: f32 = 0.2;
+const TLD_WEIGHT: f32 = 0.1;
+
+// whatlang reports ISO 639-3; everything downstream (html lang, stemmers, analyzers) uses 639-1
+const ISO_639_1: &[(&str, &str)] = &[
+    ("afr", "af"), ("aka", "ak"), ("amh", "am"), ("ara", "ar"), ("aze", "az"), ("bel", "be"),
+    ("ben", "bn"), ("bul", "bg"), ("cat", "ca"), ("ces", "cs"), ("cmn", "zh"), ("dan", "da"),
+    ("deu", "de"), ("ell", "el"), ("eng", "en"), ("epo", "eo"), ("est", "et"), ("fin", "fi"),
+    ("fra", "fr"), ("guj", "gu"), ("heb", "he"), ("hin", "hi"), ("hrv", "hr"), ("hun", "hu"),
+    ("hye", "hy"), ("ind", "id"), ("ita", "it"), ("jav", "jv"), ("jpn", "ja"), ("kan", "kn"),
+    ("kat", "ka"), ("khm", "km"), ("kor", "ko"), ("lat", "la"), ("lav", "lv"), ("lit", "lt"),
+    ("mal", "ml"), ("mar", "mr"), ("mkd", "mk"), ("mya", "my"), ("nep", "ne"), ("nld", "nl"),
+    ("nob", "nb"), ("ori", "or"), ("pan", "pa"), ("pes", "fa"), ("pol", "pl"), ("por", "pt"),
+    ("ron", "ro"), ("rus", "ru"), ("sin", "si"), ("slk", "sk"), ("slv", "sl"), ("sna", "sn"),
+    ("spa", "es"), ("srp", "sr"), ("swe", "sv"), ("tam", "ta"), ("tel", "te"), ("tgl", "tl"),
+    ("tha", "th"), ("tuk", "tk"), ("tur", "tr"), ("ukr", "uk"), ("urd", "ur"), ("uzb", "uz"),
+    ("vie", "vi"), ("yid", "yi"), ("zul", "zu"),
+];
+
+/// Default whatlang confidence a content detection must exceed to be trusted
+pub const DEFAULT_MIN_CONFIDENCE: f64 = 0.7;
+
+// Languages returned by `detect_language_candidates`
+const MAX_LANGUAGE_CANDIDATES: usize = 3;
+
+// Below this many characters whatlang guesses rather than detects
+const MIN_CHUNK_DETECTION_CHARS: usize = 40;
+
+// (language, Script, REGION) subtags of a locale tag
+type LocaleParts = (String, Option<String>, Option<String>);
+
 pub struct FastLanguageDetector;
 
 impl FastLanguageDetector {
-    /// Ultra-fast language detection combining URL analysis and content detection
-    pub fn detect_language(text: &str, url: &str) -> Option<String> {
+    /// Ultra-fast language detection combining URL analysis and content detection;
+    /// content detections at or below `min_confidence` are discarded
+    pub fn detect_language(text: &str, url: &str, min_confidence: f64) -> Option<String> {
         // Early filtering for empty content
         if text.trim().is_empty() {
             return None;
@@ -50,11 +98,11 @@ impl FastLanguageDetector {
         }
         
         // 3. Use whatlang for content detection (still very fast)
-        Self::detect_from_content(text)
+        Self::detect_from_content(text, min_confidence)
     }
     
     /// Check if content is English using fast detection
-    pub fn is_english(text: &str, url: &str) -> bool {
+    pub fn is_english(text: &str, url: &str, min_confidence: f64) -> bool {
             // If text is empty, rely solely on URL detection
             if text.trim().is_empty() {
                 return Self::detect_from_url(url)
@@ -63,7 +111,7 @@ impl FastLanguageDetector {
             }
             
             // For non-empty text, use the full detection pipeline
-            Self::detect_language(text, url)
+            Self::detect_language(text, url, min_confidence)
                 .map(|lang| lang == "en")
                 .unwrap_or(false)
     }
@@ -155,38 +203,197 @@ fn detect_from_url(url: &str) -> Option<String> {
         None
     }
     
+    /// Language of one chunk of text (no URL fallback); None for text too short to classify
+    pub fn detect_chunk_language(text: &str) -> Option<String> {
+        if text.trim().chars().count() < MIN_CHUNK_DETECTION_CHARS {
+            return None;
+        }
+        Self::detect_from_content(text, DEFAULT_MIN_CONFIDENCE)
+    }
+
+    /// Language of a page's main content. Detection runs on the extracted text rather
+    /// than raw HTML; when that is inconclusive (mixed-language pages) the largest
+    /// contiguous text block decides. The <html lang> value wins unless content
+    /// detection disagrees with it at high confidence. Returns (code, confidence).
+    pub fn detect_content_language(main_content: &str, largest_block: Option<&str>, html_lang: Option<&str>) -> Option<(String, f64)> {
+        // Confidence above which content detection overrides a conflicting html lang
+        const OVERRIDE_CONFIDENCE: f64 = 0.9;
+        // Reported confidence when the declared html lang is used
+        const HTML_LANG_CONFIDENCE: f64 = 0.6;
+
+        let content_detection = Self::detect_with_confidence(main_content)
+            .filter(|(_, confidence)| *confidence > DEFAULT_MIN_CONFIDENCE)
+            .or_else(|| largest_block.and_then(Self::detect_with_confidence))
+            .filter(|(_, confidence)| *confidence > DEFAULT_MIN_CONFIDENCE);
+        let html_lang = html_lang
+            .map(|lang| lang.trim().to_lowercase())
+            .and_then(|lang| lang.split(['-', '_']).next().map(str::to_string))
+            .filter(|lang| lang.len() == 2 || lang.len() == 3);
+
+        match (content_detection, html_lang) {
+            (Some((lang, confidence)), Some(declared)) if lang != declared => {
+                if confidence >= OVERRIDE_CONFIDENCE {
+                    Some((lang, confidence))
+                } else {
+                    Some((declared, HTML_LANG_CONFIDENCE))
+                }
+            }
+            (Some(detected), _) => Some(detected),
+            (None, Some(declared)) => Some((declared, HTML_LANG_CONFIDENCE)),
+            (None, None) => None,
+        }
+    }
+
+    /// Reconcile page locale signals into one BCP-47 tag ("en-GB") with a confidence.
+    ///
+    /// Precedence, for the language and the region independently:
+    /// 1. `<html lang>`
+    /// 2. `og:locale`
+    /// 3. the hreflang of the alternate link pointing at this page (x-default is ignored)
+    /// 4. the country-code TLD (region always; language only when nothing else declares one)
+    ///
+    /// So lang="en" on a .co.uk host resolves to en-GB.
+    pub fn resolve_locale(html_lang: Option<&str>, og_locale: Option<&str>, hreflang: Option<&str>, url: &str) -> Option<(String, f32)> {
+        let declared: Vec<(LocaleParts, f32)> = [
+            (html_lang, HTML_LANG_WEIGHT),
+            (og_locale, OG_LOCALE_WEIGHT),
+            (hreflang, HREFLANG_WEIGHT),
+        ]
+        .into_iter()
+        .filter_map(|(tag, weight)| Some((Self::parse_locale_tag(tag?)?, weight)))
+        .collect();
+
+        let tld_region = Self::country_tld(url);
+        let tld_language = tld_region.as_deref().and_then(|tld| {
+            CCTLD_LANGUAGES.iter().find(|(code, _)| *code == tld).map(|(_, lang)| lang.to_string())
+        });
+
+        let language = declared.first()
+            .map(|((language, _, _), _)| language.clone())
+            .or_else(|| tld_language.clone())?;
+        let script = declared.iter()
+            .filter(|((lang, _, _), _)| *lang == language)
+            .find_map(|((_, script, _), _)| script.clone());
+        let region = declared.iter()
+            .filter(|((lang, _, _), _)| *lang == language)
+            .find_map(|((_, _, region), _)| region.clone())
+            .or_else(|| tld_region.as_deref().map(|tld| if tld == "uk" { "GB".to_string() } else { tld.to_uppercase() }));
+
+        // A signal agrees when its language matches and it names no other region
+        let mut confidence: f32 = declared.iter()
+            .filter(|((lang, _, sig_region), _)| *lang == language && (sig_region.is_none() || *sig_region == region))
+            .map(|(_, weight)| weight)
+            .sum();
+        if tld_region.is_some() && tld_language.as_deref().is_none_or(|lang| lang == language) {
+            confidence += TLD_WEIGHT;
+        }
+
+        let mut tag = language;
+        if let Some(script) = script {
+            tag = format!("{}-{}", tag, script);
+        }
+        if let Some(region) = region {
+            tag = format!("{}-{}", tag, region);
+        }
+        Some((tag, confidence.min(1.0)))
+    }
+
+    /// Split "en_us" / "zh-hant-tw" into (language, Script, REGION) in BCP-47 casing
+    fn parse_locale_tag(tag: &str) -> Option<LocaleParts> {
+        let mut subtags = tag.trim().split(['-', '_']);
+        let language = subtags.next()?.to_lowercase();
+        if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        let mut script = None;
+        let mut region = None;
+        for subtag in subtags {
+            match subtag.len() {
+                4 if script.is_none() && region.is_none() && subtag.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    let mut chars = subtag.chars();
+                    script = chars.next().map(|first| {
+                        first.to_ascii_uppercase().to_string() + &chars.as_str().to_lowercase()
+                    });
+                }
+                2 if region.is_none() && subtag.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    region = Some(subtag.to_uppercase());
+                }
+                3 if region.is_none() && subtag.chars().all(|c| c.is_ascii_digit()) => {
+                    region = Some(subtag.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        Some((language, script, region))
+    }
+
+    /// Two-letter country-code TLD of `url`, ignoring ccTLDs used generically (.io, .co, ...)
+    pub fn country_tld(url: &str) -> Option<String> {
+        let parsed = Url::parse(url).ok()?;
+        let tld = parsed.domain()?.rsplit('.').next()?.to_lowercase();
+        (tld.len() == 2 && !GENERIC_CCTLDS.contains(&tld.as_str())).then_some(tld)
+    }
+
     /// Detect language from content using whatlang
-    fn detect_from_content(text: &str) -> Option<String> {
+    fn detect_from_content(text: &str, min_confidence: f64) -> Option<String> {
+        // Only return if confidence is reasonable
+        Self::detect_with_confidence(text)
+            .filter(|(_, confidence)| *confidence > min_confidence)
+            .map(|(lang, _)| lang)
+    }
+
+    /// whatlang detection with its ISO 639-1 code (639-3 when there is none) and confidence
+    fn detect_with_confidence(text: &str) -> Option<(String, f64)> {
         // Clean text for better detection
         let clean_text = Self::clean_text_for_detection(text);
         
         // Use whatlang for ultra-fast detection
-        if let Some(info) = detect(&clean_text) {
-            let lang_code = match info.lang() {
-                Lang::Eng => "en",
-                Lang::Spa => "es", 
-                Lang::Fra => "fr",
-                Lang::Deu => "de",
-                Lang::Ita => "it",
-                Lang::Por => "pt",
-                Lang::Rus => "ru",
-                Lang::Jpn => "ja",
-                Lang::Kor => "ko",
-                Lang::Cmn => "zh",
-                _ => return None, // Reject other languages
-            };
-            
-            // Only return if confidence is reasonable
-            if info.confidence() > 0.7 {
-                Some(lang_code.to_string())
-            } else {
-                None
+        detect(&clean_text).map(|info| (Self::language_code(info.lang()), info.confidence()))
+    }
+    
+    // ISO 639-1 code of a whatlang language, or its 639-3 code when it has none
+    fn language_code(lang: Lang) -> String {
+        let iso3 = lang.code();
+        ISO_639_1.iter()
+            .find(|(three, _)| *three == iso3)
+            .map_or(iso3, |(_, two)| two)
+            .to_string()
+    }
+
+    /// Up to MAX_LANGUAGE_CANDIDATES (code, confidence) pairs for ambiguous, mixed-language
+    /// text. whatlang names a single winner, so runners-up come from re-running it with the
+    /// earlier winners denied; each candidate carries the confidence whatlang reported for
+    /// it in that run. Only the first entry is pinned: it is always `detect_language`'s
+    /// answer (URL and <html lang> rules included), so it may rank below the next one. It
+    /// keeps its whatlang confidence when whatlang found it, else `get_language_info`'s.
+    /// The remaining entries are sorted by descending confidence.
+    pub fn detect_language_candidates(text: &str, url: &str) -> Vec<(String, f64)> {
+        let clean_text = Self::clean_text_for_detection(text);
+        let mut denied: Vec<Lang> = Vec::new();
+        let mut candidates: Vec<(String, f64)> = Vec::new();
+        while candidates.len() < MAX_LANGUAGE_CANDIDATES {
+            let Some(info) = Detector::with_denylist(denied.clone()).detect(&clean_text) else { break };
+            denied.push(info.lang());
+            if info.confidence() > 0.0 {
+                candidates.push((Self::language_code(info.lang()), info.confidence()));
             }
-        } else {
-            None
         }
+
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let (best, best_confidence, _) = Self::get_language_info(text, url);
+        let Some(best) = best else { return candidates };
+        let confidence = candidates.iter()
+            .position(|(code, _)| *code == best)
+            .map(|i| candidates.remove(i).1)
+            .unwrap_or(best_confidence);
+        candidates.insert(0, (best, confidence));
+        candidates.truncate(MAX_LANGUAGE_CANDIDATES);
+        candidates
     }
-    
+
     /// Clean text for better language detection
     fn clean_text_for_detection(text: &str) -> String {
         // Remove HTML tags, URLs, and other noise
@@ -207,9 +414,13 @@ fn detect_from_url(url: &str) -> Option<String> {
             .collect::<Vec<_>>()
             .join(" ");
         
-        // Take first 1000 characters for fast detection
+        // Take first 1000 bytes for fast detection, cutting on a char boundary
         if clean.len() > 1000 {
-            clean.truncate(1000);
+            let mut cut = 1000;
+            while !clean.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            clean.truncate(cut);
         }
         
         clean
@@ -217,7 +428,7 @@ fn detect_from_url(url: &str) -> Option<String> {
     
     /// Get detailed language detection info
     pub fn get_language_info(text: &str, url: &str) -> (Option<String>, f64, bool) {
-        let detected_lang = Self::detect_language(text, url);
+        let detected_lang = Self::detect_language(text, url, DEFAULT_MIN_CONFIDENCE);
         let is_english_domain = !url.is_empty() && Self::detect_from_url(url) == Some("en".to_string());
         
         // Calculate confidence based on detection method
@@ -230,3 +441,27 @@ fn detect_from_url(url: &str) -> Option<String> {
         (detected_lang, confidence, is_english_domain)
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_latin_scripts_map_to_their_iso_codes() {
+        let arabic = "اللغة العربية هي إحدى أكثر اللغات انتشارا في العالم ويتحدث بها أكثر من أربعمائة مليون شخص";
+        let hindi = "हिन्दी भारत की सबसे अधिक बोली जाने वाली भाषा है और इसे देवनागरी लिपि में लिखा जाता है";
+        assert_eq!(FastLanguageDetector::detect_language(arabic, "", DEFAULT_MIN_CONFIDENCE).as_deref(), Some("ar"));
+        assert_eq!(FastLanguageDetector::detect_language(hindi, "", DEFAULT_MIN_CONFIDENCE).as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn min_confidence_decides_borderline_snippets() {
+        // whatlang is about 0.88 confident this is English
+        let snippet = "The weather is nice and the children play outside";
+        assert_eq!(FastLanguageDetector::detect_language(snippet, "", 0.5).as_deref(), Some("en"));
+        assert_eq!(FastLanguageDetector::detect_language(snippet, "", 0.9), None);
+        assert!(FastLanguageDetector::is_english(snippet, "", 0.5));
+        assert!(!FastLanguageDetector::is_english(snippet, "", 0.9));
+    }
+}
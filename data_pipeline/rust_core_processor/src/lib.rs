@@ -1,20 +1,28 @@
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
-use std::collections::HashMap;
+use pyo3::types::{PyDict, PyList};
+use std::collections::{HashMap, HashSet};
 use regex::Regex;
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
 
 mod extractor;
 mod cleaner;
 mod types;
 mod language_detector;
 mod scorer;
+mod config;
+mod feed;
+mod phone;
+mod math;
 
 use extractor::OptimizedExtractor;
+use extractor::main_content_extractor;
+use extractor::metadata_extractor::MetadataExtractor;
 use cleaner::FastCleaner;
-use types::ProcessedDocument;
-use language_detector::FastLanguageDetector;
+use types::{ChunkDiagnostic, ChunkWithContext, ProcessedDocument, TokenCounts};
+use language_detector::{FastLanguageDetector, DEFAULT_MIN_CONFIDENCE};
 use scorer::ContentScorer; 
+use config::{AbbreviationMode, ProcessingConfig};
 
 // Global regex patterns compiled once
 static WHITESPACE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
@@ -58,19 +66,23 @@ fn remove_unwanted_tags(html: &str) -> String {
     cleaned
 }
 
-/// Standalone ultra-fast language detection function
+/// Standalone ultra-fast language detection function. Content detections need a whatlang
+/// confidence above `min_confidence` (default 0.7); lower it for short snippets.
 #[pyfunction]
-fn detect_language_fast(text: String, url: String) -> PyResult<PyObject> {
+#[pyo3(signature = (text, url, min_confidence=DEFAULT_MIN_CONFIDENCE))]
+fn detect_language_fast(text: String, url: String, min_confidence: f64) -> PyResult<PyObject> {
     Python::with_gil(|py| {
-        let detected_lang = FastLanguageDetector::detect_language(&text, &url);
+        let detected_lang = FastLanguageDetector::detect_language(&text, &url, min_confidence);
         Ok(detected_lang.to_object(py))
     })
 }
 
-/// Check if content is English (optimized for filtering)
+/// Check if content is English (optimized for filtering), with the same `min_confidence`
+/// gate as `detect_language_fast`
 #[pyfunction] 
-fn is_english_fast(text: String, url: String) -> PyResult<bool> {
-    Ok(FastLanguageDetector::is_english(&text, &url))
+#[pyo3(signature = (text, url, min_confidence=DEFAULT_MIN_CONFIDENCE))]
+fn is_english_fast(text: String, url: String, min_confidence: f64) -> PyResult<bool> {
+    Ok(FastLanguageDetector::is_english(&text, &url, min_confidence))
 }
 
 /// Get detailed language detection information
@@ -88,100 +100,694 @@ fn get_language_info_fast(text: String, url: String) -> PyResult<PyObject> {
     })
 }
 
-/// Main function exposed to Python - processes HTML and returns structured data
+/// Candidate languages as (code, confidence) pairs with whatlang's confidences, for
+/// mixed-language text. The first candidate is the language `detect_language_fast` returns
+/// for the same input; the rest are sorted by descending confidence.
 #[pyfunction]
-fn process_html(html_content: String, url: String) -> PyResult<PyObject> {
+fn detect_language_candidates(text: String, url: String) -> PyResult<Vec<(String, f64)>> {
+    Ok(FastLanguageDetector::detect_language_candidates(&text, &url))
+}
+
+/// Score anchor-text quality (0.0 junk .. 1.0 descriptive) for link-graph weighting
+#[pyfunction]
+fn score_anchor_text(text: String, href: String) -> PyResult<f32> {
+    Ok(OptimizedExtractor::anchor_text_quality(&text, &href))
+}
+
+/// Token count of `text` under the same tokenizer as the `token_counts` output
+#[pyfunction]
+fn count_tokens(text: String) -> PyResult<usize> {
+    Ok(FastCleaner::count_tokens(&text))
+}
+
+/// Truncate text for previews at a sentence or word boundary
+#[pyfunction]
+fn make_preview(text: String, max_chars: usize) -> PyResult<String> {
+    Ok(FastCleaner::new().make_preview(&text, max_chars))
+}
+
+/// Focused extractor for social previews: every og:* property (repeated keys such
+/// as og:image become lists) plus "image", the first og:image resolved against og:url
+#[pyfunction]
+fn extract_open_graph(html: String) -> PyResult<PyObject> {
+    let dom = tl::parse(&html, tl::ParserOptions::default())
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("HTML parse failed: {}", e)))?;
+    let metadata_extractor = MetadataExtractor::new(&dom, dom.parser());
+    let open_graph = metadata_extractor.get_open_graph();
+
+    let first_value = |key: &str| {
+        open_graph.iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, values)| values.first().cloned())
+    };
+    let base_url = first_value("og:url").and_then(|u| url::Url::parse(&u).ok());
+    let image = first_value("og:image")
+        .or_else(|| first_value("og:image:url"))
+        .or_else(|| first_value("og:image:secure_url"))
+        .map(|img| match &base_url {
+            Some(base) => base.join(&img).map(|u| u.to_string()).unwrap_or(img),
+            None => img,
+        });
+
     Python::with_gil(|py| {
-        let result = internal_process_html(html_content, url);
-        
-        match result {
-            Ok(doc) => {
-                let dict = PyDict::new_bound(py);
-                
-            // Set basic fields
-            dict.set_item("main_content", &doc.main_content)?;
-            dict.set_item("title", &doc.title)?;
-            dict.set_item("description", &doc.description)?;
-            dict.set_item("content_categories", &doc.content_categories)?;
-            dict.set_item("content_type", &doc.content_type)?;
-            dict.set_item("keywords", doc.keywords.to_object(py))?;
-            dict.set_item("headings", doc.headings.to_object(py))?;
-            dict.set_item("primary_image", doc.primary_image.to_object(py))?;
-            dict.set_item("favicon", doc.favicon.to_object(py))?;
-            dict.set_item("author_name", doc.author_name.to_object(py))?;
-            dict.set_item("published_date", doc.published_date.to_object(py))?;
-            dict.set_item("modified_date", doc.modified_date.to_object(py))?;
-            dict.set_item("canonical_url", doc.canonical_url.to_object(py))?;
-            dict.set_item("semantic_info", doc.semantic_info.to_object(py))?;
-            dict.set_item("text_chunks_with_context", doc.text_chunks_with_context.to_object(py))?;
-            dict.set_item("word_count", &doc.word_count)?;
-            dict.set_item("content_quality_score", &doc.content_quality_score)?;
-            dict.set_item("is_technical_content", &doc.is_technical_content)?;                Ok(dict.into())
-            }
-            Err(e) => {
-                let dict = PyDict::new_bound(py);
-                dict.set_item("error", format!("Processing failed: {}", e))?;
-                dict.set_item("main_content", "")?;
-                dict.set_item("title", "")?;
-                dict.set_item("description", "")?;
-                dict.set_item("keywords", Vec::<String>::new())?;
-                dict.set_item("text_chunks", Vec::<String>::new())?;
-                Ok(dict.into())
-            }
+        let dict = open_graph_dict(py, open_graph.iter().map(|(key, values)| (key, values)))?;
+        dict.set_item("image", image)?;
+        Ok(dict.into())
+    })
+}
+
+/// Lightweight link-unfurling preview ({ title, description, image, site_name }) from
+/// page metadata only: no content walk, no chunking
+#[pyfunction]
+fn extract_preview(html: String, url: String) -> PyResult<PyObject> {
+    let dom = tl::parse(&html, tl::ParserOptions::default())
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("HTML parse failed: {}", e)))?;
+    let metadata_extractor = MetadataExtractor::new(&dom, dom.parser());
+
+    let base_url = url::Url::parse(&url).ok();
+    let image = metadata_extractor.get_primary_image(|src| match &base_url {
+        Some(base) => base.join(src).map(|u| u.to_string()).unwrap_or_else(|_| src.to_string()),
+        None => src.to_string(),
+    });
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("title", metadata_extractor.get_title())?;
+        dict.set_item("description", metadata_extractor.get_description())?;
+        dict.set_item("image", image.map(|img| img.src))?;
+        dict.set_item("site_name", metadata_extractor.get_site_name())?;
+        Ok(dict.into())
+    })
+}
+
+/// URL variants of a page for dedup: { canonical, amphtml, alternates: [{ hreflang, url }],
+/// mobile }, resolved against `url`, from <link> tags only (no content walk)
+#[pyfunction]
+fn extract_url_variants(html: String, url: String) -> PyResult<PyObject> {
+    let dom = tl::parse(&html, tl::ParserOptions::default())
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("HTML parse failed: {}", e)))?;
+    let metadata_extractor = MetadataExtractor::new(&dom, dom.parser());
+
+    let base_url = url::Url::parse(&url).ok();
+    let resolve = |href: &str| match &base_url {
+        Some(base) => base.join(href).map(|u| u.to_string()).unwrap_or_else(|_| href.to_string()),
+        None => href.to_string(),
+    };
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("canonical", metadata_extractor.get_canonical_url(&url))?;
+        dict.set_item("amphtml", metadata_extractor.get_amphtml_url(resolve))?;
+        let alternates = PyList::empty_bound(py);
+        for (hreflang, alternate_url) in metadata_extractor.get_hreflang_alternates(resolve) {
+            let alternate = PyDict::new_bound(py);
+            alternate.set_item("hreflang", hreflang)?;
+            alternate.set_item("url", alternate_url)?;
+            alternates.append(alternate)?;
         }
+        dict.set_item("alternates", alternates)?;
+        dict.set_item("mobile", metadata_extractor.get_mobile_url(resolve))?;
+        Ok(dict.into())
     })
 }
 
-/// Internal processing function that does the actual work
-fn internal_process_html(html_content: String, url: String) -> Result<ProcessedDocument, Box<dyn std::error::Error>> {
-    //Remove unwanted tags BEFORE parsing to prevent CSS/script content from being extracted
-    let cleaned_html = remove_unwanted_tags(&html_content);
-    
-    
-    // Initialize processors
-    let extractor = OptimizedExtractor::new();
-    let cleaner = FastCleaner::new();
-    let scorer = ContentScorer::new(); 
+/// srcset candidates of every responsive <img> as [{ src, alt, sizes, candidates:
+/// [{ url, width, density }] }], URLs resolved against `url`
+#[pyfunction]
+fn extract_responsive_images(html: String, url: String) -> PyResult<Vec<PyObject>> {
+    let dom = tl::parse(&html, tl::ParserOptions::default())
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("HTML parse failed: {}", e)))?;
+    let metadata_extractor = MetadataExtractor::new(&dom, dom.parser());
+
+    let base_url = url::Url::parse(&url).ok();
+    let images = metadata_extractor.get_responsive_images(|src| match &base_url {
+        Some(base) => base.join(src).map(|u| u.to_string()).unwrap_or_else(|_| src.to_string()),
+        None => src.to_string(),
+    });
+    Python::with_gil(|py| Ok(images.iter().map(|image| image.to_object(py)).collect()))
+}
+
+/// Phone numbers from tel: links and page text as [{ raw, e164 }], normalized to E.164
+/// using the page's address country or ccTLD as the dialing region
+#[pyfunction]
+fn extract_phone_numbers(html: String, url: String) -> PyResult<Vec<PyObject>> {
+    let dom = tl::parse(&html, tl::ParserOptions::default())
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("HTML parse failed: {}", e)))?;
+    let metadata_extractor = MetadataExtractor::new(&dom, dom.parser());
+    let region = phone::infer_region(metadata_extractor.get_address_country(), &url);
+    let numbers = phone::extract_phone_numbers(&dom, dom.parser(), region.as_deref());
+    Python::with_gil(|py| Ok(numbers.iter().map(|n| n.to_object(py)).collect()))
+}
+
+/// Parse an RSS 2.0 or Atom feed into entries (title, link, published, summary, author),
+/// resolving relative links against `base_url`
+#[pyfunction]
+fn parse_feed(xml: String, base_url: String) -> PyResult<Vec<PyObject>> {
+    let parsed = feed::parse_feed(&xml, &base_url);
+    Python::with_gil(|py| Ok(parsed.items.iter().map(|item| item.to_object(py)).collect()))
+}
+
+// Extracted words below this share of a JSON-LD wordCount suggest truncated content
+const TRUNCATION_RATIO: f32 = 0.5;
+
+// Optional extraction features compiled into this build, for callers gating pipeline stages
+const CAPABILITIES: &[&str] = &[
+    "feeds", "previews", "open_graph", "phone_numbers", "recipes", "howto",
+    "content_language", "locale_resolution", "markdown_tables", "content_hash", "math", "threads", "responsive_images", "geo", "links", "definitions", "tables", "code_blocks",
+];
+
+/// Crate version, build identifier (git commit) and capability flags of this extension
+#[pyfunction]
+fn processor_info() -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("version", env!("CARGO_PKG_VERSION"))?;
+        dict.set_item("build", env!("BUILD_GIT_HASH"))?;
+        dict.set_item("capabilities", CAPABILITIES.to_vec())?;
+        Ok(dict.into())
+    })
+}
+
+/// og:* entries as a dict: a single value as a string, repeated tags (og:image) as a list
+fn open_graph_dict<'py, 'a>(
+    py: Python<'py>,
+    entries: impl IntoIterator<Item = (&'a String, &'a Vec<String>)>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    for (key, values) in entries {
+        if values.len() == 1 {
+            dict.set_item(key, &values[0])?;
+        } else {
+            dict.set_item(key, values)?;
+        }
+    }
+    Ok(dict)
+}
+
+/// First author as the `author_info` dict the search backend reads: name, bio, social_links
+fn author_info_dict<'py>(py: Python<'py>, doc: &ProcessedDocument) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("name", &doc.author_name)?;
+    dict.set_item("bio", &doc.author_bio)?;
+    dict.set_item("social_links", &doc.author_social_links)?;
+    Ok(dict)
+}
+
+/// Dict returned when a payload cannot be processed as an HTML page
+fn error_dict<'py>(py: Python<'py>, message: String, reason: &str) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("error", message)?;
+    dict.set_item("reason", reason)?;
+    dict.set_item("main_content", "")?;
+    dict.set_item("title", "")?;
+    dict.set_item("description", "")?;
+    dict.set_item("keywords", Vec::<String>::new())?;
+    dict.set_item("text_chunks", Vec::<String>::new())?;
+    Ok(dict)
+}
+
+/// Main function exposed to Python - processes HTML and returns structured data.
+/// Optional keyword arguments tune the pipeline (see `ProcessingConfig`).
+#[pyfunction]
+#[pyo3(signature = (html_content, url, **options))]
+fn process_html(py: Python<'_>, html_content: String, url: String, options: Option<&Bound<'_, PyDict>>) -> PyResult<PyObject> {
+    let processor = Processor::with_config(ProcessingConfig::from_py_options(options)?);
+    processor.process_dict(py, html_content, url, None)
+}
+
+// Result for one payload, computed without the GIL and turned into a dict afterwards
+enum Outcome {
+    Document(Box<ProcessedDocument>),
+    Feed(types::FeedDocument),
+    Failed { message: String, reason: &'static str },
+}
+
+/// `process_html` over a list of (html, url) pairs, returning one result dict per pair in
+/// order. Extraction runs in parallel with the GIL released; a failing document (or a
+/// panic while processing it) yields an error dict in its slot instead of aborting the batch.
+#[pyfunction]
+#[pyo3(signature = (items, **options))]
+fn process_html_batch(py: Python<'_>, items: Vec<(String, String)>, options: Option<&Bound<'_, PyDict>>) -> PyResult<Vec<PyObject>> {
+    let processor = Processor::with_config(ProcessingConfig::from_py_options(options)?);
+
+    let outcomes: Vec<Outcome> = py.allow_threads(|| {
+        items.into_par_iter().map(|(html_content, url)| {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| processor.outcome(html_content, url)))
+                .unwrap_or_else(|_| Outcome::Failed { message: "Processing panicked".to_string(), reason: "processing_failed" })
+        }).collect()
+    });
+
+    outcomes.into_iter().map(|outcome| processor.outcome_dict(py, outcome, None)).collect()
+}
+
+/// Like `process_html`, but the result holds only the keys named in `fields`
+/// (e.g. ["title", "main_content"]); chunking is skipped unless chunks are requested
+#[pyfunction]
+#[pyo3(signature = (html_content, url, fields, **options))]
+fn process_html_fields(py: Python<'_>, html_content: String, url: String, fields: Vec<String>, options: Option<&Bound<'_, PyDict>>) -> PyResult<PyObject> {
+    let mut config = ProcessingConfig::from_py_options(options)?;
+    let fields: HashSet<String> = fields.into_iter().collect();
+    config.build_chunks = fields.contains("text_chunks_with_context") || fields.contains("chunk_diagnostics");
+
+    Processor::with_config(config).process_dict(py, html_content, url, Some(&fields))
+}
+
+/// Every field the extractor produces, with all optional outputs enabled, as JSON.
+/// Meant as a golden-file target for integration tests, not for production use.
+#[pyfunction]
+fn extract_full_debug(html: String, url: String) -> PyResult<String> {
+    Processor::with_config(ProcessingConfig::all_outputs())
+        .process_document(html, url)
+        .map(|doc| doc.to_json())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Processing failed: {}", e)))
+}
+
+/// Quality score (0-1) of an already-extracted document (JSON as stored from
+/// `extract_full_debug` or `ProcessedDocument::to_json`), without re-parsing HTML. `weights`
+/// overrides quality component weights by name ("length", "structure", ...). `url` is taken
+/// for parity with the processing entry points; the quality score does not depend on it.
+#[pyfunction]
+#[pyo3(signature = (doc_json, url, weights=None))]
+fn rescore_document(doc_json: String, url: String, weights: Option<&Bound<'_, PyDict>>) -> PyResult<f32> {
+    let _ = url;
+    let doc: ProcessedDocument = serde_json::from_str(&doc_json)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid document JSON: {}", e)))?;
+    let mut scorer = ContentScorer::with_config(ProcessingConfig::default());
+    if let Some(weights) = weights {
+        let overrides: HashMap<String, f32> = weights.extract()?;
+        scorer = scorer.with_weights(&overrides).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    Ok(scorer.calculate_content_quality_score(&doc))
+}
+
+/// Result dict for a processed document; with `fields`, only those keys are converted
+fn document_dict<'py>(
+    py: Python<'py>,
+    doc: &ProcessedDocument,
+    config: &ProcessingConfig,
+    fields: Option<&HashSet<String>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    // Values are only converted for wanted keys, so unrequested chunk lists never cross into Python
+    macro_rules! set {
+        ($key:expr, $value:expr) => {
+            if fields.is_none_or(|f| f.contains($key)) {
+                dict.set_item($key, $value)?;
+            }
+        };
+    }
+
+    set!("main_content", &doc.main_content);
+    if let Some(raw) = &doc.main_content_raw {
+        set!("main_content_raw", raw);
+    }
+    if let Some(content_html) = &doc.main_content_html {
+        set!("main_content_html", content_html);
+    }
+    set!("content_region_offsets", doc.content_region_offsets.to_object(py));
+    set!("content_source", &doc.content_source);
+    set!("title", &doc.title);
+    set!("description", &doc.description);
+    set!("content_categories", &doc.content_categories);
+    set!("news_section", doc.news_section.to_object(py));
+    set!("content_type", &doc.content_type);
+    set!("content_type_source", &doc.content_type_source);
+    if config.content_type_alternatives {
+        set!("content_type_confidence", doc.content_type_confidence);
+        set!("content_type_alternatives", doc.content_type_alternatives.to_object(py));
+    }
+    set!("resolved_locale", doc.resolved_locale.to_object(py));
+    set!("resolved_locale_confidence", doc.resolved_locale_confidence);
+    set!("detected_language", doc.language.to_object(py));
+    if config.detect_content_language {
+        set!("content_language", doc.content_language.to_object(py));
+        set!("content_language_confidence", doc.content_language_confidence);
+    }
+    set!("content_gated", doc.content_gated);
+    set!("declared_word_count", doc.declared_word_count);
+    set!("content_truncation_suspected", doc.content_truncation_suspected);
+    set!("read_more_truncated", doc.read_more_truncated);
+    set!("gate_reason", doc.gate_reason.to_object(py));
+    set!("is_placeholder_content", doc.is_placeholder_content);
+    set!("duplicate_metadata", doc.duplicate_metadata);
+    set!("has_encoding_errors", doc.has_encoding_errors);
+    set!("encoding_error_count", doc.encoding_error_count);
+    set!("is_error_page", doc.is_error_page);
+    set!("error_page_reason", doc.error_page_reason.to_object(py));
+    set!("keywords", doc.keywords.to_object(py));
+    if !config.keyword_synonyms.is_empty() {
+        set!("keyword_expansions", doc.keyword_expansions.to_object(py));
+    }
+    set!("headings", doc.headings.to_object(py));
+    set!("sections", doc.sections.to_object(py));
+    set!("lead_paragraph", doc.lead_paragraph.to_object(py));
+    set!("quotes", doc.quotes.to_object(py));
+    if config.definitions {
+        set!("definitions", &doc.definitions);
+    }
+    if config.abbreviations == AbbreviationMode::Synonyms {
+        set!("abbreviations", doc.abbreviations.to_object(py));
+    }
+    set!("primary_image", doc.primary_image.to_object(py));
+    set!("favicon", doc.favicon.to_object(py));
+    set!("theme_color", doc.theme_color.to_object(py));
+    set!("open_graph", open_graph_dict(py, &doc.open_graph)?);
+    set!("twitter_cards", doc.twitter_cards.to_object(py));
+    if config.responsive_images {
+        set!("responsive_images", doc.responsive_images.to_object(py));
+    }
+    set!("media", doc.media.to_object(py));
+    set!("code_blocks", doc.code_blocks.to_object(py));
+    set!("tables", doc.tables.to_object(py));
+    set!("links", doc.links.to_object(py));
+    set!("author_name", doc.author_name.to_object(py));
+    set!("authors", doc.authors.to_object(py));
+    set!("author_info", author_info_dict(py, doc)?);
+    if config.phone_numbers {
+        set!("phone_numbers", doc.phone_numbers.to_object(py));
+    }
+    set!("geo", doc.geo.to_object(py));
+    set!("thread", doc.thread.to_object(py));
+    set!("math_blocks", doc.math_blocks.to_object(py));
+    set!("author_count", doc.authors.len());
+    set!("published_date", doc.published_date.to_object(py));
+    set!("modified_date", doc.modified_date.to_object(py));
+    set!("content_staleness_days", doc.content_staleness_days);
+    set!("durations", doc.durations.to_object(py));
+    if let Some(recipe) = &doc.recipe {
+        set!("recipe", recipe.to_object(py));
+    }
+    if let Some(howto) = &doc.howto {
+        set!("howto", howto.to_object(py));
+    }
+    if let Some(product) = &doc.product {
+        set!("product", product.to_object(py));
+    }
+    set!("json_ld_blocks_skipped", doc.json_ld_blocks_skipped);
+    set!("canonical_url", doc.canonical_url.to_object(py));
+    set!("is_self_canonical", doc.is_self_canonical);
+    set!("semantic_info", doc.semantic_info.to_object(py));
+    set!("text_chunks_with_context", doc.text_chunks_with_context.to_object(py));
+    if config.detect_low_effort {
+        set!("low_effort_score", doc.low_effort_score);
+    }
+    if config.token_counts {
+        set!("token_counts", doc.token_counts.to_object(py));
+    }
+    if config.collect_warnings {
+        set!("warnings", doc.warnings.to_object(py));
+    }
+    if config.chunk_diagnostics {
+        set!("chunk_diagnostics", doc.chunk_diagnostics.to_object(py));
+    }
+    set!("word_count", doc.word_count);
+    set!("content_hash", &doc.content_hash);
+    set!("content_quality_score", doc.content_quality_score);
+    // content_quality_score already is the ContentScorer result; the explicit names let
+    // rankers consume it and the domain authority without digging into semantic_info
+    set!("scorer_quality_score", doc.content_quality_score);
+    set!("domain_score", doc.semantic_info.domain_score);
+    set!("is_technical_content", doc.is_technical_content);
+    Ok(dict)
+}
+
+/// Reusable processing pipeline: the extractor, cleaner and scorer (and the regexes they
+/// compile) are built once from the options and shared by every document. Long-running
+/// workers keep one per option set; `process_html` builds a temporary one per call.
+#[pyclass]
+struct Processor {
+    config: ProcessingConfig,
+    extractor: OptimizedExtractor,
+    cleaner: FastCleaner,
+    scorer: ContentScorer,
+    volatile_patterns: Vec<Regex>,
+}
+
+#[pymethods]
+impl Processor {
+    #[new]
+    #[pyo3(signature = (**options))]
+    fn py_new(options: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        Ok(Self::with_config(ProcessingConfig::from_py_options(options)?))
+    }
+
+    /// Same result as `process_html(html_content, url, **options)` with this processor's options
+    fn process(&self, py: Python<'_>, html_content: String, url: String) -> PyResult<PyObject> {
+        self.process_dict(py, html_content, url, None)
+    }
+}
+
+impl Processor {
+    fn with_config(config: ProcessingConfig) -> Self {
+        let volatile_patterns = config.volatile_patterns.iter()
+            .filter_map(|p| Regex::new(p).ok())
+            .collect();
+        Self {
+            extractor: OptimizedExtractor::with_config(config.clone()),
+            cleaner: FastCleaner::with_config(&config),
+            scorer: ContentScorer::with_config(config.clone()),
+            volatile_patterns,
+            config,
+        }
+    }
+
+    /// Result dict of one payload: the document, or an error dict for denied domains,
+    /// feeds and failures. With `fields`, only those keys are converted.
+    fn process_dict(&self, py: Python<'_>, html_content: String, url: String, fields: Option<&HashSet<String>>) -> PyResult<PyObject> {
+        let outcome = self.outcome(html_content, url);
+        self.outcome_dict(py, outcome, fields)
+    }
+
+    /// Deny-listed domains and feed payloads short-circuit; everything else is processed
+    fn outcome(&self, html_content: String, url: String) -> Outcome {
+        if self.config.is_denied_domain(&url) {
+            return Outcome::Failed { message: format!("Domain is on the deny-list: {}", url), reason: "denied_domain" };
+        }
+        // RSS/Atom payloads are not pages: hand back the parsed feed instead of running HTML extraction
+        if feed::is_feed_payload(&html_content) {
+            return Outcome::Feed(feed::parse_feed(&html_content, &url));
+        }
+
+        match self.process_document(html_content, url) {
+            Ok(doc) => Outcome::Document(Box::new(doc)),
+            Err(e) => Outcome::Failed { message: format!("Processing failed: {}", e), reason: "processing_failed" },
+        }
+    }
+
+    fn outcome_dict(&self, py: Python<'_>, outcome: Outcome, fields: Option<&HashSet<String>>) -> PyResult<PyObject> {
+        Ok(match outcome {
+            Outcome::Document(doc) => document_dict(py, &doc, &self.config, fields)?.into(),
+            Outcome::Feed(parsed) => {
+                let dict = error_dict(py, "Payload is a feed, not an HTML page".to_string(), "feed")?;
+                dict.set_item("feed", parsed.to_object(py))?;
+                dict.into()
+            }
+            Outcome::Failed { message, reason } => error_dict(py, message, reason)?.into(),
+        })
+    }
+
+    /// The actual work: extraction, cleaning, chunk filtering and scoring
+    fn process_document(&self, html_content: String, url: String) -> Result<ProcessedDocument, Box<dyn std::error::Error>> {
+        let (config, extractor, cleaner, scorer) = (&self.config, &self.extractor, &self.cleaner, &self.scorer);
+        //Remove unwanted tags BEFORE parsing to prevent CSS/script content from being extracted
+        let cleaned_html = remove_unwanted_tags(&html_content);
     
-    // Extract all content from the cleaned HTML in one pass
-    let mut doc = extractor.extract_content(&cleaned_html, &url);
+        // Extract all content from the cleaned HTML in one pass
+        let mut doc = extractor.extract_content(&cleaned_html, &url)?;
     
-    // ⚡ CLEAN ALL DATES using the FastCleaner for OpenSearch compatibility
-    doc.published_date = cleaner.normalize_date(doc.published_date.as_deref().unwrap_or(""));
-    doc.modified_date = cleaner.normalize_date(doc.modified_date.as_deref().unwrap_or(""));
+        doc.content_staleness_days = FastCleaner::staleness_days(
+            doc.published_date.as_deref(),
+            doc.modified_date.as_deref(),
+            config.now,
+        );
     
-    // Clean and process the text (only for English content)
-    doc.main_content = cleaner.clean_text(&doc.main_content);
-    doc.description = cleaner.clean_description(&doc.description);
-    // 🧹 CRITICAL: Clean ALL chunks using FastCleaner for proper noise removal
-    for chunk in &mut doc.text_chunks_with_context {
-        chunk.text_chunk = cleaner.clean_text(&chunk.text_chunk);
-    }
+        // Markdown tables, definition lists and fenced code carry line-break placeholders through cleaning; restore them afterwards
+        let clean = |text: &str| {
+            // Fenced code skips FastCleaner so URLs, emails and entities in it survive
+            let cleaned = if config.strip_encoding_errors {
+                main_content_extractor::clean_outside_code(&text.replace(char::REPLACEMENT_CHARACTER, ""), |prose| cleaner.clean_text(prose))
+            } else {
+                main_content_extractor::clean_outside_code(text, |prose| cleaner.clean_text(prose))
+            };
+            if config.uses_line_breaks() { main_content_extractor::restore_line_breaks(&cleaned) } else { cleaned }
+        };
+
+        // Keep the pre-clean text for auditing over-aggressive cleaning
+        if config.include_raw_content {
+            doc.main_content_raw = Some(main_content_extractor::restore_line_breaks(&doc.main_content));
+        }
+
+        // Bytes decoded with the wrong charset surface as U+FFFD; count them before any stripping
+        doc.encoding_error_count = doc.main_content.matches(char::REPLACEMENT_CHARACTER).count();
+        doc.has_encoding_errors = doc.encoding_error_count > 0;
+        if config.collect_warnings && doc.has_encoding_errors {
+            doc.warnings.push(format!("encoding_errors:{}", doc.encoding_error_count));
+        }
+
+        // Clean and process the text (only for English content)
+        doc.main_content = clean(&doc.main_content);
+        doc.description = cleaner.clean_description(&doc.description);
+        doc.lead_paragraph = doc.lead_paragraph.as_deref()
+            .map(|lead| cleaner.clean_text(lead))
+            .filter(|lead| !lead.is_empty());
+        // 🧹 CRITICAL: Clean ALL chunks using FastCleaner for proper noise removal
+        // Book-length documents clean their chunks on the rayon pool; order is untouched
+        if doc.text_chunks_with_context.len() > config.parallel_chunk_threshold {
+            doc.text_chunks_with_context.par_iter_mut().for_each(|chunk| {
+                chunk.text_chunk = clean(&chunk.text_chunk);
+            });
+        } else {
+            for chunk in &mut doc.text_chunks_with_context {
+                chunk.text_chunk = clean(&chunk.text_chunk);
+            }
+        }
     
-    // Filter out chunks that became too small or empty after cleaning (reduced minimum length)
-    doc.text_chunks_with_context.retain(|chunk| {
-        !chunk.text_chunk.is_empty() && chunk.text_chunk.len() >= 25  // Reduced from 50 to 25
-    });
+        // Filter out chunks that became too small or empty after cleaning (reduced minimum length)
+        let (kept, dropped): (Vec<_>, Vec<_>) = std::mem::take(&mut doc.text_chunks_with_context)
+            .into_iter()
+            .partition(|chunk| !chunk.text_chunk.is_empty() && chunk.text_chunk.len() >= 25);  // Reduced from 50 to 25
+        if config.collect_warnings && !dropped.is_empty() {
+            doc.warnings.push(format!("chunks_dropped_after_cleaning:{}", dropped.len()));
+        }
+        if config.chunk_diagnostics {
+            doc.chunk_diagnostics.extend(dropped.iter().map(|chunk| ChunkDiagnostic {
+                chunk_index: chunk.chunk_index,
+                reason: "too_short_after_cleaning".to_string(),
+                preview: chunk.text_chunk.chars().take(80).collect(),
+            }));
+        }
+        doc.text_chunks_with_context = if kept.is_empty() && config.guarantee_chunks {
+            dropped.into_iter()
+                .filter(|chunk| !chunk.text_chunk.is_empty())
+                .map(|chunk| ChunkWithContext { low_confidence: true, ..chunk })
+                .collect()
+        } else {
+            kept
+        };
     
-    let final_quality_score = scorer.calculate_content_quality_score(&doc);
-    let domain_score = scorer.calculate_domain_score(&url);
-    // Calculate content quality metrics
-    doc.word_count = doc.main_content.split_whitespace().count();
-    doc.content_quality_score = final_quality_score;
-    doc.semantic_info.content_quality_score = final_quality_score;
-    doc.semantic_info.domain_score = domain_score;
+        if let Some(template) = &config.chunk_context_template {
+            for chunk in &mut doc.text_chunks_with_context {
+                // An H1 repeating the title adds nothing to the path; chunks outside any section
+                // fall back to their nearest heading
+                let path: Vec<&str> = chunk.section_path.iter()
+                    .map(String::as_str)
+                    .skip_while(|heading| heading.eq_ignore_ascii_case(doc.title.trim()))
+                    .collect();
+                let section = if path.is_empty() {
+                    chunk.relevant_headings.first().cloned().unwrap_or_default()
+                } else {
+                    path.join(" > ")
+                };
+                chunk.contextualized_text = Some(template
+                    .replace("{title}", &doc.title)
+                    .replace("{section}", &section)
+                    .replace("{chunk}", &chunk.text_chunk));
+            }
+        }
+        if config.detect_low_effort {
+            doc.low_effort_score = scorer.calculate_low_effort_score(&doc);
+        }
+        let final_quality_score = scorer.calculate_content_quality_score(&doc);
+        let domain_score = scorer.calculate_domain_score(&url);
+        // Calculate content quality metrics
+        doc.word_count = doc.main_content.split_whitespace().count();
+        // Publisher-declared length far above what was extracted: paywall or failed extraction
+        doc.content_truncation_suspected = doc.declared_word_count
+            .is_some_and(|declared| (doc.word_count as f32) < declared as f32 * TRUNCATION_RATIO);
+        if config.collect_warnings && doc.content_truncation_suspected {
+            doc.warnings.push(format!("content_truncated:{}/{}", doc.word_count, doc.declared_word_count.unwrap_or(0)));
+        }
+        doc.content_hash = cleaner.stable_content_hash(&doc.main_content, &self.volatile_patterns);
+        if config.token_counts {
+            doc.token_counts = TokenCounts {
+                main_content: FastCleaner::count_tokens(&doc.main_content),
+                title: FastCleaner::count_tokens(&doc.title),
+                description: FastCleaner::count_tokens(&doc.description),
+                chunks: doc.text_chunks_with_context.iter().map(|c| FastCleaner::count_tokens(&c.text_chunk)).sum(),
+            };
+        }
+        doc.content_quality_score = final_quality_score;
+        doc.semantic_info.content_quality_score = final_quality_score;
+        doc.semantic_info.domain_score = domain_score;
     
-    Ok(doc)
+        Ok(doc)
+    }
 }
 
 
 /// Python module definition
 #[pymodule]
 fn rust_core_processor(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Processor>()?;
     m.add_function(wrap_pyfunction!(process_html, m)?)?;
+    m.add_function(wrap_pyfunction!(process_html_fields, m)?)?;
+    m.add_function(wrap_pyfunction!(process_html_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_full_debug, m)?)?;
+    m.add_function(wrap_pyfunction!(rescore_document, m)?)?;
     m.add_function(wrap_pyfunction!(detect_language_fast, m)?)?;
     m.add_function(wrap_pyfunction!(is_english_fast, m)?)?;
     m.add_function(wrap_pyfunction!(get_language_info_fast, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_language_candidates, m)?)?;
+    m.add_function(wrap_pyfunction!(make_preview, m)?)?;
+    m.add_function(wrap_pyfunction!(count_tokens, m)?)?;
+    m.add_function(wrap_pyfunction!(score_anchor_text, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_open_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_feed, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_preview, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_phone_numbers, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_url_variants, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_responsive_images, m)?)?;
+    m.add_function(wrap_pyfunction!(processor_info, m)?)?;
     Ok(())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(body: &str) -> String {
+        format!("<html><head><title>Garden notes</title></head><body><article>{}</article></body></html>", body)
+    }
+
+    #[test]
+    fn replacement_characters_are_counted_as_encoding_errors() {
+        let processor = Processor::with_config(ProcessingConfig::default());
+        let paragraph = "The caf\u{FFFD} on the corner serves cr\u{FFFD}pes every morning to the regulars who stop by. ";
+        let doc = processor.process_document(article(&format!("<p>{}</p>", paragraph.repeat(2))), "https://example.com/".to_string()).unwrap();
+        assert!(doc.has_encoding_errors);
+        assert_eq!(doc.encoding_error_count, 4);
+    }
+
+    #[test]
+    fn contextualized_chunks_carry_title_and_nearest_heading() {
+        let processor = Processor::with_config(ProcessingConfig {
+            chunk_context_template: Some("{title} / {section}: {chunk}".to_string()),
+            ..ProcessingConfig::default()
+        });
+        let paragraph = "Tomatoes need full sun, steady watering and a stake once the first trusses set fruit. ";
+        let body = format!("<h1>Garden notes</h1><h2>Growing tomatoes</h2><p>{}</p>", paragraph.repeat(6));
+        let doc = processor.process_document(article(&body), "https://example.com/".to_string()).unwrap();
+        assert!(!doc.text_chunks_with_context.is_empty());
+        for chunk in &doc.text_chunks_with_context {
+            let text = chunk.contextualized_text.as_deref().unwrap();
+            assert!(text.starts_with("Garden notes / Growing tomatoes: "), "{}", text);
+        }
+    }
+
+    #[test]
+    fn far_fewer_words_than_declared_flags_truncation() {
+        let processor = Processor::with_config(ProcessingConfig::default());
+        let page = |word_count: usize| format!(
+            "<html><head><script type=\"application/ld+json\">{{\"@type\": \"Article\", \"wordCount\": {}}}</script></head>\
+             <body><article><p>{}</p></article></body></html>",
+            word_count,
+            "Only the opening paragraph is visible before the paywall asks readers to subscribe. ".repeat(3),
+        );
+        let truncated = processor.process_document(page(2400), "https://example.com/".to_string()).unwrap();
+        assert_eq!(truncated.declared_word_count, Some(2400));
+        assert!(truncated.content_truncation_suspected);
+        let complete = processor.process_document(page(40), "https://example.com/".to_string()).unwrap();
+        assert!(!complete.content_truncation_suspected);
+    }
+}
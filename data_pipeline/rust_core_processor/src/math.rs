@@ -0,0 +1,120 @@
+use regex::Regex;
+use once_cell::sync::Lazy;
+use std::ops::Range;
+use tl::{Node, Parser, VDom};
+use crate::cleaner::FastCleaner;
+use crate::types::MathBlock;
+
+// TeX delimiters, display forms first so "$$" is never read as two empty inline spans
+static TEX_SPAN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)\$\$(.+?)\$\$|\\\[(.+?)\\\]|\\\((.+?)\\\)|\$([^\s$](?:[^$]*?[^\s$])?)\$").unwrap()
+});
+
+/// Math in the body in document order: <math> elements serialized as MathML, MathJax
+/// `<script type="math/tex">` sources, and $...$, $$...$$, \(...\), \[...\] TeX spans
+/// in text. Sources are taken from the DOM, so FastCleaner never touches them.
+pub fn extract_math_blocks(dom: &VDom, parser: &Parser) -> Vec<MathBlock> {
+    let mut blocks = Vec::new();
+    if let Some(body) = dom.query_selector("body").and_then(|mut iter| iter.next()).and_then(|h| h.get(parser)) {
+        collect_math(body, parser, &mut blocks);
+    }
+    blocks
+}
+
+/// MathBlock for a <math> or MathJax script element, None for any other node
+pub fn element_math(node: &Node, parser: &Parser) -> Option<MathBlock> {
+    let tag = node.as_tag()?;
+    let attrs = tag.attributes();
+    match tag.name().as_utf8_str().to_lowercase().as_str() {
+        "math" => Some(MathBlock {
+            format: "mathml".to_string(),
+            source: tag.raw().as_utf8_str().trim().to_string(),
+            display: attrs.get("display").flatten().map(|d| d.as_utf8_str() == "block").unwrap_or(false),
+        }),
+        "script" => {
+            let script_type = attrs.get("type").flatten()?.as_utf8_str().to_lowercase();
+            let mode = script_type.strip_prefix("math/tex")?;
+            let source = FastCleaner::decode_entities(tag.inner_text(parser).trim());
+            (!source.is_empty()).then(|| MathBlock {
+                format: "tex".to_string(),
+                source,
+                display: mode.contains("mode=display"),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Replace each TeX span in `text` with "[math:N]", N indexing its entry in `blocks`;
+/// spans with no matching block are left as written
+pub fn tex_placeholders(text: &str, blocks: &[MathBlock]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, block) in tex_spans(text) {
+        if let Some(index) = block_index(blocks, &FastCleaner::decode_entities(&block.source)) {
+            out.push_str(&text[last..range.start]);
+            out.push_str(&placeholder(index));
+            last = range.end;
+        }
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// Index of the block with `source`, for placeholders
+pub fn block_index(blocks: &[MathBlock], source: &str) -> Option<usize> {
+    blocks.iter().position(|b| b.source == source)
+}
+
+pub fn placeholder(index: usize) -> String {
+    format!("[math:{}]", index)
+}
+
+fn collect_math(node: &Node, parser: &Parser, blocks: &mut Vec<MathBlock>) {
+    if let Some(block) = element_math(node, parser) {
+        blocks.push(block);
+        return;
+    }
+    match node {
+        Node::Tag(tag) => {
+            let name = tag.name().as_utf8_str().to_lowercase();
+            // Shell variables and prices in code samples are not TeX
+            if matches!(name.as_str(), "script" | "style" | "noscript" | "template" | "code" | "pre" | "kbd" | "samp") {
+                return;
+            }
+            for child in tag.children().top().iter().filter_map(|c| c.get(parser)) {
+                collect_math(child, parser, blocks);
+            }
+        }
+        Node::Raw(text) => {
+            let text = FastCleaner::decode_entities(&text.as_utf8_str());
+            blocks.extend(tex_spans(&text).into_iter().map(|(_, block)| block));
+        }
+        Node::Comment(_) => {}
+    }
+}
+
+fn tex_spans(text: &str) -> Vec<(Range<usize>, MathBlock)> {
+    let mut spans = Vec::new();
+    for caps in TEX_SPAN.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let (source, display) = match (caps.get(1), caps.get(2), caps.get(3), caps.get(4)) {
+            (Some(m), ..) | (_, Some(m), ..) => (m.as_str(), true),
+            (_, _, Some(m), _) => (m.as_str(), false),
+            (.., Some(m)) => {
+                // "$5 and $10": a closing dollar that opens an amount is currency
+                if text[whole.end()..].starts_with(|c: char| c.is_ascii_digit()) {
+                    continue;
+                }
+                (m.as_str(), false)
+            }
+            _ => continue,
+        };
+        let source = source.trim();
+        if source.is_empty() {
+            continue;
+        }
+        spans.push((whole.range(), MathBlock { format: "tex".to_string(), source: source.to_string(), display }));
+    }
+    spans
+}
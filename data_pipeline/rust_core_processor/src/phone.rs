@@ -0,0 +1,119 @@
+use regex::Regex;
+use once_cell::sync::Lazy;
+use phonenumber::Mode;
+use tl::{Node, Parser, VDom};
+use crate::language_detector::FastLanguageDetector;
+use crate::types::PhoneNumber;
+
+// Digit runs that look like a phone number: optional +CC, optional (area), then
+// 2-5 digit groups joined by spaces, dots or dashes
+static PHONE_CANDIDATE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{1,4}\)[\s.-]?)?\d{2,5}(?:[\s.-]\d{2,5}){1,4}\b").unwrap()
+});
+// Dates, times-of-year and year ranges that share the phone shape
+static NON_PHONE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:\d{4}[-./]\d{1,2}[-./]\d{1,2}|\d{1,2}[-./]\d{1,2}[-./]\d{2,4}|\d{4}\s*-\s*\d{4})$").unwrap()
+});
+
+/// Phone numbers from tel: links and visible text. Numbers are normalized to E.164 when
+/// they carry a +country code or `region` (ISO-3166 alpha-2) is known; text matches that
+/// cannot be validated are dropped, tel: links are kept raw. Deduplicated, document order.
+pub fn extract_phone_numbers(dom: &VDom, parser: &Parser, region: Option<&str>) -> Vec<PhoneNumber> {
+    let country = region.and_then(|r| r.to_uppercase().parse::<phonenumber::country::Id>().ok());
+    let mut numbers: Vec<PhoneNumber> = Vec::new();
+    let mut push = |number: PhoneNumber| {
+        let key = number.e164.clone().unwrap_or_else(|| digits(&number.raw));
+        if !numbers.iter().any(|n| n.e164.clone().unwrap_or_else(|| digits(&n.raw)) == key) {
+            numbers.push(number);
+        }
+    };
+
+    for tag in dom.query_selector("a").into_iter().flatten().filter_map(|h| h.get(parser)?.as_tag()) {
+        let Some(href) = tag.attributes().get("href").flatten() else { continue };
+        let href = href.as_utf8_str();
+        let Some(raw) = href.strip_prefix("tel:").or_else(|| href.strip_prefix("TEL:")) else { continue };
+        // RFC 3966 parameters (";ext=", ";phone-context=") are not part of the number
+        let raw = raw.split(';').next().unwrap_or("").trim();
+        if digits(raw).len() < 3 {
+            continue;
+        }
+        push(PhoneNumber { raw: raw.to_string(), e164: normalize(raw, country) });
+    }
+
+    let mut text = String::new();
+    if let Some(body) = dom.query_selector("body").and_then(|mut iter| iter.next()).and_then(|h| h.get(parser)) {
+        collect_visible_text(body, parser, &mut text);
+    }
+    for candidate in PHONE_CANDIDATE.find_iter(&text) {
+        let raw = candidate.as_str().trim();
+        let digit_count = digits(raw).len();
+        if !(7..=15).contains(&digit_count) || NON_PHONE.is_match(raw) {
+            continue;
+        }
+        if let Some(e164) = normalize(raw, country) {
+            push(PhoneNumber { raw: raw.to_string(), e164: Some(e164) });
+        }
+    }
+
+    numbers
+}
+
+/// Dialing region for national-format numbers: the page's postal address country,
+/// else the ccTLD (".uk" is GB)
+pub fn infer_region(address_country: Option<String>, url: &str) -> Option<String> {
+    address_country.or_else(|| {
+        FastLanguageDetector::country_tld(url).map(|tld| if tld == "uk" { "GB".to_string() } else { tld.to_uppercase() })
+    })
+}
+
+/// E.164 form of `raw` if it parses as a valid number for its +prefix or `country`
+fn normalize(raw: &str, country: Option<phonenumber::country::Id>) -> Option<String> {
+    if country.is_none() && !raw.starts_with('+') {
+        return None;
+    }
+    let number = phonenumber::parse(country, raw).ok()?;
+    phonenumber::is_valid(&number).then(|| number.format().mode(Mode::E164).to_string())
+}
+
+fn digits(text: &str) -> String {
+    text.chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+// Body text with one separator per node, skipping scripts and styles
+fn collect_visible_text(node: &Node, parser: &Parser, out: &mut String) {
+    match node {
+        Node::Tag(tag) => {
+            let name = tag.name().as_utf8_str().to_lowercase();
+            if matches!(name.as_str(), "script" | "style" | "noscript" | "template") {
+                return;
+            }
+            for child in tag.children().top().iter().filter_map(|c| c.get(parser)) {
+                collect_visible_text(child, parser, out);
+            }
+        }
+        Node::Raw(text) => {
+            out.push_str(&text.as_utf8_str().replace("&nbsp;", " "));
+            // A non-space separator so digit groups never join across nodes
+            out.push_str(" | ");
+        }
+        Node::Comment(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tel_links_and_text_numbers_normalize_to_e164() {
+        let html = r#"<html><body>
+            <a href="tel:+44-20-7946-0958;ext=12">Call the London office</a>
+            <p>Berlin office: 030 2639 8000, open weekdays. Founded 2001-2005.</p>
+        </body></html>"#;
+        let dom = tl::parse(html, tl::ParserOptions::default()).unwrap();
+        let numbers = extract_phone_numbers(&dom, dom.parser(), Some("de"));
+        let e164: Vec<Option<&str>> = numbers.iter().map(|n| n.e164.as_deref()).collect();
+        assert_eq!(e164, vec![Some("+442079460958"), Some("+493026398000")]);
+        assert_eq!(numbers[0].raw, "+44-20-7946-0958");
+    }
+}
@@ -3,6 +3,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use url::Url;
 use crate::types::{ProcessedDocument, SemanticInfo};
+use crate::config::ProcessingConfig;
 
 // --- Static HashMaps and Vecs for keywords using Lazy ---
 
@@ -79,11 +80,61 @@ static INSTITUTIONAL_INDICATORS: Lazy<Vec<&str>> = Lazy::new(|| {
     vec!["university", "institute", "research center", "official", "documentation", "specification", "standard", "rfc", "ieee", "acm"]
 });
 
-pub struct ContentScorer;
+// Stock connective phrases of formulaic (often machine-written) prose
+static TEMPLATED_PHRASES: Lazy<Vec<&str>> = Lazy::new(|| {
+    vec![
+        "in conclusion", "in summary", "in today's", "it is important to note", "it's important to note",
+        "whether you're", "let's dive", "dive into", "furthermore", "moreover", "additionally",
+        "ultimately", "in the world of", "when it comes to", "plays a crucial role", "a testament to",
+    ]
+});
+
+// Boilerplate section headings of templated articles
+static TEMPLATED_HEADINGS: Lazy<Vec<&str>> = Lazy::new(|| {
+    vec!["introduction", "conclusion", "final thoughts", "key takeaways", "faq", "faqs", "frequently asked questions", "summary"]
+});
+
+// Sentences needed before the low-effort heuristic says anything
+const MIN_LOW_EFFORT_SENTENCES: usize = 5;
+
+// Quality component weights, summed in this fixed order so the float result is identical across runs
+const QUALITY_WEIGHTS: [(&str, f32); 8] = [
+    ("length", 0.2),
+    ("structure", 0.2),
+    ("content_type", 0.15),
+    ("language", 0.1),
+    ("metadata", 0.1),
+    ("technical", 0.1),
+    ("authoritativeness", 0.1),
+    ("completeness", 0.05),
+];
+
+// Raw weighted score mapped to 1.0; excellent pages reach about 1.5 before clamping
+const RAW_QUALITY_CEILING: f32 = 2.0;
+
+// Ratio of all words to content words in ordinary prose
+const CONTENT_WORD_SCALE: usize = 2;
+
+pub struct ContentScorer {
+    config: ProcessingConfig,
+    weights: [(&'static str, f32); 8],
+}
 
 impl ContentScorer {
-    pub fn new() -> Self {
-        Self {}
+    pub fn with_config(config: ProcessingConfig) -> Self {
+        Self { config, weights: QUALITY_WEIGHTS }
+    }
+
+    /// Replaces the named quality component weights; components not mentioned keep their
+    /// default. Errors on a name that is not a quality component.
+    pub fn with_weights(mut self, overrides: &HashMap<String, f32>) -> Result<Self, String> {
+        for (name, &weight) in overrides {
+            match self.weights.iter_mut().find(|(k, _)| k == name) {
+                Some(entry) => entry.1 = weight,
+                None => return Err(format!("unknown quality weight {:?}", name)),
+            }
+        }
+        Ok(self)
     }
 
     pub fn calculate_domain_score(&self, url_str: &str) -> f32 {
@@ -107,32 +158,95 @@ impl ContentScorer {
         0.3 // Default score
     }
 
+    /// The authoritative content quality score, on a 0-1 scale (1 = best). The weighted
+    /// component sum is divided by RAW_QUALITY_CEILING and clamped, so every output field
+    /// and weight set reports on the same scale.
     pub fn calculate_content_quality_score(&self, doc: &ProcessedDocument) -> f32 {
-        if doc.main_content.is_empty() { return 0.1; }
+        (self.raw_quality_score(doc) / RAW_QUALITY_CEILING).clamp(0.0, 1.0)
+    }
 
-        let weights: HashMap<&str, f32> = [
-            ("length", 0.2),
-            ("structure", 0.2),
-            ("content_type", 0.15),
-            ("language", 0.1),
-            ("metadata", 0.1),
-            ("technical", 0.1),
-            ("authoritativeness", 0.1),
-            ("completeness", 0.05),
-        ].iter().cloned().collect();
+    // Weighted sum of the component scores, each around 1.0 for an unremarkable page
+    fn raw_quality_score(&self, doc: &ProcessedDocument) -> f32 {
+        if doc.main_content.is_empty() { return 0.1; }
 
+        // Indicator and citation scans run on a bounded prefix of very large documents
+        let content = self.config.analysis_sample(&doc.main_content);
         let scores: HashMap<&str, f32> = [
-            ("length", self.calculate_length_score(doc.word_count)),
+            ("length", self.calculate_length_score(self.length_basis(doc))),
             ("structure", self.calculate_structure_score(doc)),
-            ("content_type", self.calculate_content_type_score(&doc.main_content, &doc.title)),
-            ("language", self.calculate_language_quality_score(&doc.main_content)),
+            ("content_type", self.calculate_content_type_score(content, &doc.title)),
+            ("language", self.calculate_language_quality_score(content)),
             ("metadata", self.calculate_metadata_score(doc)),
-            ("technical", self.calculate_technical_bonus(&doc.main_content)),
-            ("authoritativeness", self.calculate_authoritativeness_score(&doc.main_content, &doc.title)),
+            ("technical", self.calculate_technical_bonus(content, !doc.code_blocks.is_empty())),
+            ("authoritativeness", self.calculate_authoritativeness_score(content, &doc.title)),
             ("completeness", 1.0), // Placeholder, completeness is complex
         ].iter().cloned().collect();
 
-        weights.keys().map(|&k| weights[k] * scores[k]).sum()
+        let score: f32 = self.weights.iter().map(|&(k, weight)| weight * scores[k]).sum();
+
+        // Unfilled templates should not compete with real pages in the index
+        // Mild: the low-effort signal is a heuristic and must not bury decent pages
+        let score = score * (1.0 - 0.2 * doc.low_effort_score);
+
+        if doc.is_placeholder_content { score * 0.1 } else { score }
+    }
+
+    /// Heuristic 0-1 likelihood that content is low-effort or machine-generated, from
+    /// uniform sentence lengths, low burstiness (few abrupt length changes), a lack of
+    /// citations, numbers and named entities, and stock phrases / boilerplate headings.
+    /// A heuristic signal only, NOT a detector: human formulaic writing scores high too.
+    /// 0.0 when there are too few sentences to judge.
+    pub fn calculate_low_effort_score(&self, doc: &ProcessedDocument) -> f32 {
+        let content = self.config.analysis_sample(&doc.main_content);
+        let sentence_lengths: Vec<f32> = content
+            .split(['.', '!', '?'])
+            .map(|sentence| sentence.split_whitespace().count() as f32)
+            .filter(|&words| words >= 3.0)
+            .collect();
+        if sentence_lengths.len() < MIN_LOW_EFFORT_SENTENCES {
+            return 0.0;
+        }
+
+        // Uniformity: coefficient of variation of sentence length; human prose is usually above 0.5
+        let mean = sentence_lengths.iter().sum::<f32>() / sentence_lengths.len() as f32;
+        let variance = sentence_lengths.iter().map(|l| (l - mean).powi(2)).sum::<f32>() / sentence_lengths.len() as f32;
+        let uniformity = (1.0 - variance.sqrt() / mean / 0.6).clamp(0.0, 1.0);
+
+        // Burstiness: share of neighbouring sentences whose lengths differ by half or more
+        let bursts = sentence_lengths.windows(2)
+            .filter(|pair| (pair[0] - pair[1]).abs() >= 0.5 * pair[0].max(pair[1]))
+            .count() as f32 / (sentence_lengths.len() - 1) as f32;
+        let low_burstiness = (1.0 - bursts / 0.4).clamp(0.0, 1.0);
+
+        // Specificity: citations, numbers and mid-sentence capitalized words per 100 words
+        let words: Vec<&str> = content.split_whitespace().collect();
+        let citations: usize = CITATION_PATTERNS.iter().map(|pat| pat.find_iter(&content.to_lowercase()).count()).sum();
+        let specifics = words.windows(2)
+            .filter(|pair| !pair[0].ends_with(['.', '!', '?', ':']))
+            .filter(|pair| pair[1].starts_with(|c: char| c.is_uppercase() || c.is_ascii_digit()))
+            .count();
+        let specifics_per_100 = (specifics + 3 * citations) as f32 * 100.0 / words.len().max(1) as f32;
+        let unspecific = (1.0 - specifics_per_100 / 8.0).clamp(0.0, 1.0);
+
+        // Templating: stock phrases per sentence plus boilerplate headings
+        let content_lower = content.to_lowercase();
+        let phrases: usize = TEMPLATED_PHRASES.iter().map(|p| content_lower.matches(p).count()).sum();
+        let boilerplate_headings = doc.headings.iter()
+            .filter(|h| TEMPLATED_HEADINGS.contains(&h.text.trim().trim_end_matches(':').to_lowercase().as_str()))
+            .count();
+        let templated = (phrases as f32 / sentence_lengths.len() as f32 * 3.0 + boilerplate_headings as f32 * 0.25).min(1.0);
+
+        0.3 * uniformity + 0.2 * low_burstiness + 0.3 * unspecific + 0.2 * templated
+    }
+
+    // Word count the length score is based on. Content words are about half of ordinary
+    // prose, so they are doubled to keep the thresholds; filler-heavy text falls short of them
+    fn length_basis(&self, doc: &ProcessedDocument) -> usize {
+        if self.config.content_word_length {
+            doc.semantic_info.content_word_count * CONTENT_WORD_SCALE
+        } else {
+            doc.word_count
+        }
     }
 
     fn calculate_length_score(&self, word_count: usize) -> f32 {
@@ -148,11 +262,17 @@ impl ContentScorer {
     
     fn calculate_structure_score(&self, doc: &ProcessedDocument) -> f32 {
         let mut score = 1.0;
-        if doc.main_content.contains("```") {
+        if doc.main_content.contains("```") || !doc.code_blocks.is_empty() {
             score *= 1.2;
         }
         if doc.semantic_info.headings_count >= 3 { score *= 1.15; }
         else if doc.semantic_info.headings_count >= 1 { score *= 1.05; }
+        // Hierarchical outlines (h1 > h2 > h3) read better than a flat run of same-level headings
+        match doc.semantic_info.max_heading_depth {
+            0 | 1 => {}
+            2 => score *= 1.05,
+            _ => score *= 1.1,
+        }
         
         score
     }
@@ -211,13 +331,15 @@ impl ContentScorer {
         if doc.description.len() > 50 { score *= 1.05; }
         if doc.author_name.is_some() { score *= 1.02; }
         if doc.published_date.is_some() { score *= 1.02; }
+        // Title == H1 == description: nothing beyond a heading was written for the page
+        if doc.duplicate_metadata { score *= 0.7; }
         score
     }
 
-    fn calculate_technical_bonus(&self, content: &str) -> f32 {
+    fn calculate_technical_bonus(&self, content: &str, has_code_blocks: bool) -> f32 {
     let mut score: f32 = 1.0;
         let content_lower = content.to_lowercase();
-        if content.contains("```") || content.contains("<code>") { score *= 1.25; }
+        if has_code_blocks || content.contains("```") || content.contains("<code>") { score *= 1.25; }
         if content.contains("def ") || content.contains("function ") { score *= 1.15; }
         if content_lower.contains("class ") { score *= 1.1; }
         score.min(2.5)
@@ -240,4 +362,61 @@ impl ContentScorer {
         
         score.min(2.0)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_document() -> ProcessedDocument {
+        let paragraph = "Researchers at the university published a study on river ecology. \
+            The team measured oxygen levels at 40 sites over three years. ";
+        let html = format!(
+            "<html><head><title>River ecology study</title></head><body><article><h1>River ecology</h1>\
+             <h2>Method</h2><p>{}</p><h2>Results</h2><p>{}</p></article></body></html>",
+            paragraph.repeat(8),
+            paragraph.repeat(6),
+        );
+        crate::extractor::extract_all_metadata(&html, "https://example.org/study").unwrap()
+    }
+
+    #[test]
+    fn stored_document_rescores_with_each_weight_set() {
+        let json = serde_json::to_string(&sample_document()).unwrap();
+        let doc: ProcessedDocument = serde_json::from_str(&json).unwrap();
+
+        let length_only: HashMap<String, f32> = QUALITY_WEIGHTS.iter()
+            .map(|(name, _)| (name.to_string(), if *name == "length" { 1.0 } else { 0.0 }))
+            .collect();
+        let default_score = ContentScorer::with_config(ProcessingConfig::default())
+            .calculate_content_quality_score(&doc);
+        let length_score = ContentScorer::with_config(ProcessingConfig::default())
+            .with_weights(&length_only)
+            .unwrap()
+            .calculate_content_quality_score(&doc);
+        assert_ne!(default_score, length_score);
+    }
+
+    #[test]
+    fn quality_scores_stay_on_the_zero_to_one_scale() {
+        let documents = [
+            sample_document(),
+            crate::extractor::extract_all_metadata("<html><body></body></html>", "https://example.org/").unwrap(),
+            crate::extractor::extract_all_metadata("<p>Lorem ipsum dolor sit amet</p>", "").unwrap(),
+        ];
+        let heavy: HashMap<String, f32> = QUALITY_WEIGHTS.iter()
+            .map(|(name, _)| (name.to_string(), 10.0))
+            .collect();
+        let scorers = [
+            ContentScorer::with_config(ProcessingConfig::default()),
+            ContentScorer::with_config(ProcessingConfig::default()).with_weights(&heavy).unwrap(),
+        ];
+        for doc in &documents {
+            // The extractor's provisional score and the authoritative scorer share one scale
+            assert!((0.0..=1.0).contains(&doc.content_quality_score), "{}", doc.content_quality_score);
+            for scorer in &scorers {
+                let score = scorer.calculate_content_quality_score(doc);
+                assert!((0.0..=1.0).contains(&score), "{}", score);
+            }
+        }
+    }
+}
@@ -6,31 +6,88 @@ use pyo3::prelude::*;
 pub struct ProcessedDocument {
     // Core content
     pub main_content: String,
+    pub main_content_raw: Option<String>,  // Pre-clean text, only when requested
+    pub main_content_html: Option<String>, // Inner HTML of the content region, only when requested
+    pub content_region_offsets: Option<(usize, usize)>, // Char span of main content within body text
+    pub content_source: String,            // "dom", or "app_state" when recovered from __NEXT_DATA__ etc.
     pub title: String,
     pub description: String,
     pub keywords: Vec<String>,
+    pub keyword_expansions: Vec<String>,   // Synonyms of the keywords (keyword_synonyms option)
     
     // Content structure - OPTIMIZED: Only primary image and essential headings
     pub headings: Vec<Heading>,
+    pub sections: Vec<Section>,
+    pub lead_paragraph: Option<String>,    // Standfirst/lede, for snippets
+    pub duplicate_metadata: bool,          // Title, H1 and description are identical
+    pub has_encoding_errors: bool,         // main_content carried U+FFFD replacement characters
+    pub encoding_error_count: usize,       // How many, counted before any stripping
+    pub low_effort_score: f32,             // Heuristic 0-1 formulaic/machine-written likelihood; 0 unless enabled
+    pub quotes: Vec<Quote>,                // <blockquote> material, kept apart from the author's prose
+    pub definitions: Vec<(String, String)>, // <dl> (term, definition) pairs; filled when enabled
+    pub abbreviations: HashMap<String, String>, // <abbr> text -> title expansion (synonyms mode)
     pub primary_image: Option<ImageInfo>,  // Only the main/featured image
     pub favicon: Option<String>,           // Only favicon URL
+    pub theme_color: Option<String>,       // "#RRGGBB" from theme-color / msapplication-TileColor
+    pub open_graph: HashMap<String, Vec<String>>, // og:* meta tags, keyed without the "og:" prefix; repeated tags keep every value
+    pub twitter_cards: HashMap<String, String>, // twitter:* meta tags, keyed without the "twitter:" prefix
+    pub code_blocks: Vec<CodeBlock>,       // <pre> blocks, verbatim, in document order
+    pub tables: Vec<TableInfo>,            // Data tables (2+ columns), up to max_tables
+    pub links: Vec<LinkInfo>,              // Up to MAX_LINKS distinct outbound links, document order
+    pub responsive_images: Vec<ResponsiveImage>, // srcset candidates per image; filled when enabled
+    pub media: Vec<MediaObject>,           // og:video / og:audio objects
     pub content_type: String,
+    pub content_type_confidence: f32,
+    pub content_type_source: String,       // Provenance of content_type: "json_ld", "microdata", "og_type" or "heuristic"
+    pub content_type_alternatives: Vec<ContentTypeCandidate>, // Other plausible types, best first
     // Content analysis
     pub word_count: usize,
-    pub content_quality_score: f32,
+    pub content_hash: String,              // Stable over crawls; volatile counters/dates excluded
+    pub content_quality_score: f32,        // 0-1, from ContentScorer
     pub is_technical_content: bool,
     pub content_categories: Vec<String>,
+    pub news_section: Option<String>,      // "politics", "business", ... from article:section / breadcrumb / URL
+    pub language: Option<String>,          // <html lang> primary subtag, else detected from the text
+    pub content_language: Option<String>,  // Language of main_content, only when requested
+    pub content_language_confidence: f64,
+    pub resolved_locale: Option<String>,  // Best-guess BCP-47 tag for geo-targeting, e.g. "en-GB"
+    pub resolved_locale_confidence: f32,
+    pub content_gated: bool,               // Page body is a login/registration wall
+    pub gate_reason: Option<String>,       // e.g. "login"
+    pub declared_word_count: Option<usize>, // JSON-LD wordCount of the article
+    pub content_truncation_suspected: bool, // Extracted far fewer words than declared (paywall/failed extraction)
+    pub read_more_truncated: bool,         // Teaser behind a "Read more" whose continuation is not in the DOM
+    pub is_placeholder_content: bool,      // Lorem ipsum / unfilled CMS template
+    pub is_error_page: bool,               // Soft 404: error page served as a normal page
+    pub error_page_reason: Option<String>, // "error_title" or "error_message"
     
     // Metadata - OPTIMIZED: Only essential fields
-    pub canonical_url: Option<String>,     // Only if different from URL
+    pub canonical_url: Option<String>,     // Resolved canonical link; None when the page has none
+    pub is_self_canonical: bool,           // Canonical link points at the page itself
     pub published_date: Option<String>,
     pub modified_date: Option<String>,
+    pub content_staleness_days: Option<i64>, // Days since the last update (see `now` option)
     pub author_name: Option<String>,       // Simplified author info
+    pub authors: Vec<String>,              // All credited authors, in byline/JSON-LD order
+    pub author_bio: Option<String>,        // Bio of the first author (JSON-LD Person or author box)
+    pub author_social_links: Vec<String>,  // That author's profile links (sameAs, social hosts, rel="me")
+    pub phone_numbers: Vec<PhoneNumber>,  // tel: links and numbers in the page text; filled when enabled
+    pub geo: Option<GeoInfo>,             // Page location from schema.org geo or geo metas
+    pub math_blocks: Vec<MathBlock>,      // MathML and TeX expressions, source preserved
+    pub token_counts: TokenCounts,        // Tokens per field of the final output, for index sizing
+    pub durations: Vec<DurationInfo>,      // Recipe/video durations from JSON-LD
+    pub recipe: Option<RecipeData>,        // Only for content_type "recipe"
+    pub howto: Option<HowToData>,          // JSON-LD HowTo; sets content_type "howto"
+    pub product: Option<ProductData>,      // JSON-LD or microdata Product; absent without markup
+    pub thread: Vec<ThreadPost>,          // Question/original post then replies, for forum and Q&A pages
+    pub json_ld_blocks_skipped: usize,     // JSON-LD scripts not parsed due to the configured cap
+    pub warnings: Vec<String>,             // Non-fatal extraction issues (collect_warnings option)
     
 
     
     // Chunking with context
     pub text_chunks_with_context: Vec<ChunkWithContext>,
+    pub chunk_diagnostics: Vec<ChunkDiagnostic>,  // Dropped chunks and why, only when requested
     
     // Semantic analysis
     pub semantic_info: SemanticInfo,
@@ -40,7 +97,76 @@ pub struct ProcessedDocument {
 pub struct Heading {
     pub level: u8,
     pub text: String,
-    // Removed: id, class (not useful for search)
+    pub anchor_id: Option<String>,  // DOM id when present, otherwise a generated slug
+    // Removed: class (not useful for search)
+}
+
+// Document-order section: a heading plus the text that follows it until the next heading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+    pub heading: String,
+    pub level: u8,
+    pub anchor_id: Option<String>,
+    pub text: String,
+    pub reading_ease: Option<f64>, // Flesch reading ease of text; None when too short to score
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quote {
+    pub text: String,
+    pub cite_url: Option<String>,  // blockquote cite attribute, resolved
+    pub source: Option<String>,    // Text of a <cite> inside the quote, e.g. the speaker
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentTypeCandidate {
+    pub content_type: String,
+    pub score: f32,              // 0.0-1.0; 1.0 for a declared JSON-LD @type
+    pub source: String,          // "json_ld", "microdata", "og_type" or "heuristic"
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenCounts {
+    pub main_content: usize,
+    pub title: usize,
+    pub description: usize,
+    pub chunks: usize,           // Sum over the kept text chunks
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MathBlock {
+    pub format: String,          // "mathml" (serialized <math> element) or "tex"
+    pub source: String,
+    pub display: bool,           // Display (block) math rather than inline
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhoneNumber {
+    pub raw: String,             // As written on the page or in the tel: link
+    pub e164: Option<String>,    // "+14155550123"; None when no region could be inferred
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeoInfo {
+    pub latitude: f64,           // Decimal degrees, -90..=90
+    pub longitude: f64,          // Decimal degrees, -180..=180
+    pub place_name: Option<String>,
+}
+
+// One srcset entry: exactly one of width ("480w") or density ("2x"; bare URLs are 1x)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SrcsetCandidate {
+    pub url: String,
+    pub width: Option<u32>,
+    pub density: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponsiveImage {
+    pub src: Option<String>,     // Fallback src, resolved
+    pub alt: String,
+    pub sizes: Option<String>,   // Raw sizes attribute, for width-descriptor selection
+    pub candidates: Vec<SrcsetCandidate>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,32 +176,161 @@ pub struct ImageInfo {
     // Removed: title, width, height (not essential for search)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaObject {
+    pub kind: String,                // "video" or "audio"
+    pub url: String,
+    pub secure_url: Option<String>,
+    pub mime_type: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurationInfo {
+    pub field: String,       // Source property, e.g. "cookTime" or "duration"
+    pub raw: String,         // Original ISO-8601 value, e.g. "PT1H30M"
+    pub seconds: u64,
+    pub human: String,       // Display form, e.g. "1 hr 30 min"
+}
+
+// Recipe merged from JSON-LD, microdata and h-recipe. Durations are in seconds, not minutes,
+// to match DurationInfo and HowTo total_time elsewhere in the document.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecipeData {
+    pub name: Option<String>,
+    pub ingredients: Vec<String>,
+    pub instructions: Vec<String>,
+    pub prep_time: Option<u64>,
+    pub cook_time: Option<u64>,
+    pub total_time: Option<u64>,
+    #[serde(rename = "yield")]
+    pub recipe_yield: Option<String>,
+    pub servings: Option<u32>,               // Leading number of the yield ("4 servings" -> 4)
+    pub nutrition: HashMap<String, String>,  // e.g. "calories" -> "240 kcal"
+    pub rating: Option<f64>,                 // aggregateRating ratingValue
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HowToStep {
+    pub name: Option<String>,
+    pub text: String,
+    pub image: Option<String>,   // Resolved step image URL
+}
+
+// schema.org HowTo from JSON-LD; total_time in seconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HowToData {
+    pub name: Option<String>,
+    pub steps: Vec<HowToStep>,
+    pub total_time: Option<u64>,
+}
+
+// schema.org Product with its first Offer, from JSON-LD or microdata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProductData {
+    pub name: Option<String>,
+    pub price: Option<f64>,
+    pub currency: Option<String>,       // ISO 4217 code, e.g. "USD"
+    pub availability: Option<String>,   // schema.org term without prefix, e.g. "InStock"
+    pub sku: Option<String>,
+    pub brand: Option<String>,
+    pub rating: Option<f64>,            // aggregateRating ratingValue
+    pub review_count: Option<u64>,
+}
+
+// One post of a forum or Q&A thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadPost {
+    pub role: String,            // "question", "answer", "post" (original post) or "reply"
+    pub author: Option<String>,
+    pub date: Option<String>,    // As published; not normalized
+    pub text: String,
+}
+
 // NEW: Optimized chunk with local context
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkWithContext {
     pub text_chunk: String,
     pub relevant_headings: Vec<String>,  // Only headings that apply to this chunk
     pub chunk_index: usize,
+    pub section_anchor: Option<String>,  // Anchor of the section the chunk starts in
+    pub section_path: Vec<String>,       // Headings enclosing that section, outermost first
+    pub contextualized_text: Option<String>, // Chunk rendered with title and section path, when configured
+    pub low_confidence: bool,            // Kept only because filtering would have left no chunks
+    pub language: Option<String>,        // Detected per chunk; None when too short to tell
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedItem {
+    pub title: String,
+    pub link: Option<String>,
+    pub published: Option<String>,   // pubDate / published / updated, normalized to UTC ISO-8601
+    pub summary: Option<String>,     // description / summary with markup stripped
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedDocument {
+    pub title: String,
+    pub link: Option<String>,
+    pub items: Vec<FeedItem>,
+}
+
+// A chunk rejected by the quality gates, e.g. reason "css_density"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkDiagnostic {
+    pub chunk_index: usize,
+    pub reason: String,
+    pub preview: String,   // First characters of the dropped chunk
+}
+
+// A <pre> code block with its whitespace intact; `language` is "" when undeclared
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeBlock {
+    pub language: String,
+    pub code: String,
+}
+
+// A data table of the page; `headers` is empty when the table has no header row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableInfo {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub caption: String,
+}
+
+// An outbound <a href> of the page, resolved against the page URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkInfo {
+    pub href: String,
+    pub text: String,
+    pub rel: Option<String>,
+    pub is_external: bool,       // Host differs from the page's (ignoring "www.")
+    pub anchor_quality: f32,     // OptimizedExtractor::anchor_text_quality of the text
+}
 
-// REMOVED: LinkInfo (not essential for search)
 // REMOVED: AuthorInfo (simplified to author_name string)
 // REMOVED: StructuredData (replaced with StructuredMeta)
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticInfo {
     pub word_count: usize,
+    pub content_word_count: usize,    // Words that are neither stopwords nor numbers
     pub sentence_count: usize,
     pub paragraph_count: usize,
     pub reading_time_minutes: f32,
     pub content_quality_score: f32,
     pub is_technical_content: bool,
     pub headings_count: usize,
+    pub max_heading_depth: usize,     // Deepest h1 > h2 > h3 nesting in document order; flat pages are 1
+    pub heading_levels_used: usize,   // Distinct heading levels (h1..h6) present
     pub images_count: usize,
     pub links_count: usize,
     pub technical_score: f32,
     pub avg_sentence_length: f32,
+    pub flesch_reading_ease: f32,     // 0.0 below 30 words of main content: too short to score, not a measured 0.0
+    pub flesch_kincaid_grade: f32,    // 0.0 below 30 words, as for flesch_reading_ease
     pub content_density: f32,
     pub domain_score: f32,
 }
@@ -84,22 +339,79 @@ impl Default for ProcessedDocument {
     fn default() -> Self {
         Self {
             main_content: String::new(),
+            main_content_raw: None,
+            main_content_html: None,
+            content_region_offsets: None,
+            content_source: "dom".to_string(),
             title: String::new(),
             description: String::new(),
             content_type: String::new(),
+            content_type_confidence: 0.0,
+            content_type_source: "heuristic".to_string(),
+            content_type_alternatives: Vec::new(),
             keywords: Vec::new(),
+            keyword_expansions: Vec::new(),
             headings: Vec::new(),
+            sections: Vec::new(),
+            lead_paragraph: None,
+            duplicate_metadata: false,
+            has_encoding_errors: false,
+            encoding_error_count: 0,
+            low_effort_score: 0.0,
+            quotes: Vec::new(),
+            abbreviations: HashMap::new(),
             primary_image: None,
             favicon: None,
+            theme_color: None,
+            open_graph: HashMap::new(),
+            twitter_cards: HashMap::new(),
+            code_blocks: Vec::new(),
+            tables: Vec::new(),
+            links: Vec::new(),
+            definitions: Vec::new(),
+            responsive_images: Vec::new(),
+            media: Vec::new(),
             word_count: 0,
+            content_hash: String::new(),
             content_quality_score: 0.0,
             is_technical_content: false,
             content_categories: Vec::new(),
+            news_section: None,
+            language: None,
+            content_language: None,
+            content_language_confidence: 0.0,
+            resolved_locale: None,
+            resolved_locale_confidence: 0.0,
+            content_gated: false,
+            declared_word_count: None,
+            content_truncation_suspected: false,
+            read_more_truncated: false,
+            gate_reason: None,
+            is_placeholder_content: false,
+            is_error_page: false,
+            error_page_reason: None,
             canonical_url: None,
+            is_self_canonical: false,
             published_date: None,
             modified_date: None,
+            content_staleness_days: None,
             author_name: None,
+            authors: Vec::new(),
+            author_bio: None,
+            author_social_links: Vec::new(),
+            phone_numbers: Vec::new(),
+            geo: None,
+            math_blocks: Vec::new(),
+            token_counts: TokenCounts::default(),
+            durations: Vec::new(),
+            recipe: None,
+            howto: None,
+            product: None,
+            thread: Vec::new(),
+            json_ld_blocks_skipped: 0,
+            warnings: Vec::new(),
             text_chunks_with_context: Vec::new(),
+            chunk_diagnostics: Vec::new(),
             semantic_info: SemanticInfo::default(),
         }
     }
@@ -110,28 +422,178 @@ impl Default for SemanticInfo {
     fn default() -> Self {
         Self {
             word_count: 0,
+            content_word_count: 0,
             sentence_count: 0,
             paragraph_count: 0,
             reading_time_minutes: 0.0,
             content_quality_score: 0.0,
             is_technical_content: false,
             headings_count: 0,
+            max_heading_depth: 0,
+            heading_levels_used: 0,
             images_count: 0,
             links_count: 0,
             technical_score: 0.0,
             avg_sentence_length: 0.0,
+            flesch_reading_ease: 0.0,
+            flesch_kincaid_grade: 0.0,
             content_density: 0.0,
             domain_score: 0.0,
         }
     }
 }
 
+impl ProcessedDocument {
+    /// Pretty JSON of every field. Maps are key-sorted, so output is stable across runs.
+    pub fn to_json(&self) -> String {
+        serde_json::to_value(self)
+            .and_then(|value| serde_json::to_string_pretty(&value))
+            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+    }
+}
+
 // Helper function to convert structs to Python objects
 impl ToPyObject for Heading {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         let dict = pyo3::types::PyDict::new_bound(py);
         dict.set_item("level", self.level).unwrap();
         dict.set_item("text", &self.text).unwrap();
+        dict.set_item("anchor_id", &self.anchor_id).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for Section {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("heading", &self.heading).unwrap();
+        dict.set_item("level", self.level).unwrap();
+        dict.set_item("anchor_id", &self.anchor_id).unwrap();
+        dict.set_item("word_count", self.text.split_whitespace().count()).unwrap();
+        dict.set_item("reading_ease", self.reading_ease).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for Quote {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("text", &self.text).unwrap();
+        dict.set_item("cite_url", &self.cite_url).unwrap();
+        dict.set_item("source", &self.source).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for ContentTypeCandidate {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("content_type", &self.content_type).unwrap();
+        dict.set_item("score", self.score).unwrap();
+        dict.set_item("source", &self.source).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for TokenCounts {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("main_content", self.main_content).unwrap();
+        dict.set_item("title", self.title).unwrap();
+        dict.set_item("description", self.description).unwrap();
+        dict.set_item("chunks", self.chunks).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for ThreadPost {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("role", &self.role).unwrap();
+        dict.set_item("author", &self.author).unwrap();
+        dict.set_item("date", &self.date).unwrap();
+        dict.set_item("text", &self.text).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for MathBlock {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("format", &self.format).unwrap();
+        dict.set_item("source", &self.source).unwrap();
+        dict.set_item("display", self.display).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for PhoneNumber {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("raw", &self.raw).unwrap();
+        dict.set_item("e164", &self.e164).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for GeoInfo {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("latitude", self.latitude).unwrap();
+        dict.set_item("longitude", self.longitude).unwrap();
+        dict.set_item("place_name", &self.place_name).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for SrcsetCandidate {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("url", &self.url).unwrap();
+        dict.set_item("width", self.width).unwrap();
+        dict.set_item("density", self.density).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for CodeBlock {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("language", &self.language).unwrap();
+        dict.set_item("code", &self.code).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for TableInfo {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("headers", &self.headers).unwrap();
+        dict.set_item("rows", &self.rows).unwrap();
+        dict.set_item("caption", &self.caption).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for LinkInfo {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("href", &self.href).unwrap();
+        dict.set_item("text", &self.text).unwrap();
+        dict.set_item("rel", &self.rel).unwrap();
+        dict.set_item("is_external", self.is_external).unwrap();
+        dict.set_item("anchor_quality", self.anchor_quality).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for ResponsiveImage {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("src", &self.src).unwrap();
+        dict.set_item("alt", &self.alt).unwrap();
+        dict.set_item("sizes", &self.sizes).unwrap();
+        dict.set_item("candidates", self.candidates.to_object(py)).unwrap();
         dict.into()
     }
 }
@@ -145,12 +607,127 @@ impl ToPyObject for ImageInfo {
     }
 }
 
+impl ToPyObject for MediaObject {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("kind", &self.kind).unwrap();
+        dict.set_item("url", &self.url).unwrap();
+        dict.set_item("secure_url", &self.secure_url).unwrap();
+        dict.set_item("mime_type", &self.mime_type).unwrap();
+        dict.set_item("width", self.width).unwrap();
+        dict.set_item("height", self.height).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for DurationInfo {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("field", &self.field).unwrap();
+        dict.set_item("raw", &self.raw).unwrap();
+        dict.set_item("seconds", self.seconds).unwrap();
+        dict.set_item("human", &self.human).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for ChunkDiagnostic {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("chunk_index", self.chunk_index).unwrap();
+        dict.set_item("reason", &self.reason).unwrap();
+        dict.set_item("preview", &self.preview).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for RecipeData {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("name", &self.name).unwrap();
+        dict.set_item("ingredients", &self.ingredients).unwrap();
+        dict.set_item("instructions", &self.instructions).unwrap();
+        dict.set_item("prep_time", self.prep_time).unwrap();
+        dict.set_item("cook_time", self.cook_time).unwrap();
+        dict.set_item("total_time", self.total_time).unwrap();
+        dict.set_item("yield", &self.recipe_yield).unwrap();
+        dict.set_item("servings", self.servings).unwrap();
+        dict.set_item("nutrition", &self.nutrition).unwrap();
+        dict.set_item("rating", self.rating).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for HowToStep {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("name", &self.name).unwrap();
+        dict.set_item("text", &self.text).unwrap();
+        dict.set_item("image", &self.image).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for HowToData {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("name", &self.name).unwrap();
+        dict.set_item("steps", self.steps.to_object(py)).unwrap();
+        dict.set_item("total_time", self.total_time).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for ProductData {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("name", &self.name).unwrap();
+        dict.set_item("price", self.price).unwrap();
+        dict.set_item("currency", &self.currency).unwrap();
+        dict.set_item("availability", &self.availability).unwrap();
+        dict.set_item("sku", &self.sku).unwrap();
+        dict.set_item("brand", &self.brand).unwrap();
+        dict.set_item("rating", self.rating).unwrap();
+        dict.set_item("review_count", self.review_count).unwrap();
+        dict.into()
+    }
+}
+
 impl ToPyObject for ChunkWithContext {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         let dict = pyo3::types::PyDict::new_bound(py);
         dict.set_item("text_chunk", &self.text_chunk).unwrap();
         dict.set_item("relevant_headings", &self.relevant_headings).unwrap();
         dict.set_item("chunk_index", self.chunk_index).unwrap();
+        dict.set_item("section_anchor", &self.section_anchor).unwrap();
+        dict.set_item("section_path", &self.section_path).unwrap();
+        if let Some(text) = &self.contextualized_text {
+            dict.set_item("contextualized_text", text).unwrap();
+        }
+        dict.set_item("low_confidence", self.low_confidence).unwrap();
+        dict.set_item("language", &self.language).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for FeedItem {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("title", &self.title).unwrap();
+        dict.set_item("link", &self.link).unwrap();
+        dict.set_item("published", &self.published).unwrap();
+        dict.set_item("summary", &self.summary).unwrap();
+        dict.set_item("author", &self.author).unwrap();
+        dict.into()
+    }
+}
+
+impl ToPyObject for FeedDocument {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = pyo3::types::PyDict::new_bound(py);
+        dict.set_item("title", &self.title).unwrap();
+        dict.set_item("link", &self.link).unwrap();
+        dict.set_item("items", self.items.to_object(py)).unwrap();
         dict.into()
     }
 }
@@ -160,16 +737,21 @@ impl ToPyObject for SemanticInfo {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         let dict = pyo3::types::PyDict::new_bound(py);
         dict.set_item("word_count", self.word_count).unwrap();
+        dict.set_item("content_word_count", self.content_word_count).unwrap();
         dict.set_item("sentence_count", self.sentence_count).unwrap();
         dict.set_item("paragraph_count", self.paragraph_count).unwrap();
         dict.set_item("reading_time_minutes", self.reading_time_minutes).unwrap();
         dict.set_item("content_quality_score", self.content_quality_score).unwrap();
         dict.set_item("is_technical_content", self.is_technical_content).unwrap();
         dict.set_item("headings_count", self.headings_count).unwrap();
+        dict.set_item("max_heading_depth", self.max_heading_depth).unwrap();
+        dict.set_item("heading_levels_used", self.heading_levels_used).unwrap();
         dict.set_item("images_count", self.images_count).unwrap();
         dict.set_item("links_count", self.links_count).unwrap();
         dict.set_item("technical_score", self.technical_score).unwrap();
         dict.set_item("avg_sentence_length", self.avg_sentence_length).unwrap();
+        dict.set_item("flesch_reading_ease", self.flesch_reading_ease).unwrap();
+        dict.set_item("flesch_kincaid_grade", self.flesch_kincaid_grade).unwrap();
         dict.set_item("content_density", self.content_density).unwrap();
         dict.set_item("domain_score", self.domain_score).unwrap(); 
         dict.into()